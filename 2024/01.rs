@@ -1,25 +1,19 @@
-use advent::prelude::*;
+use crate::parse::{ParseError, Source};
 
 type Input = (Vec<i32>, Vec<i32>);
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let mut l = Vec::new();
     let mut r = Vec::new();
 
     for line in input.lines() {
-        let mut it = line.split_whitespace();
-        l.push(it.next().unwrap().parse::<i32>().unwrap());
-        r.push(it.next().unwrap().parse::<i32>().unwrap());
+        let mut fields = source.fields(line, line.split_whitespace());
+        l.push(fields.parse()?);
+        r.push(fields.parse()?);
     }
 
-    (l, r)
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 01));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok((l, r))
 }
 
 fn part1((mut l, mut r): Input) -> i32 {
@@ -43,10 +37,7 @@ fn part2((l, r): Input) -> i32 {
     similarity as i32
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 01, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -55,7 +46,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 3246517);
         assert_eq!(part2(input), 29379307);
     }
@@ -69,7 +60,8 @@ mod tests {
 1   3
 3   9
 3   3",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 11);
         assert_eq!(part2(input.clone()), 31);
     }
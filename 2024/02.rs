@@ -1,25 +1,15 @@
-use advent::prelude::*;
+use crate::parse::{ParseError, Source};
 
 type Input = Vec<Vec<i64>>;
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|x| x.parse().unwrap())
-                .collect()
-        })
+        .map(|line| line.split_whitespace().map(|x| source.parse(x)).collect())
         .collect()
 }
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 02));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
-}
-
 fn part1(input: Input) -> i64 {
     let mut count = 0;
     for line in input {
@@ -66,10 +56,7 @@ fn part2(input: Input) -> i64 {
     count
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 02, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -78,7 +65,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 639);
         assert_eq!(part2(input), 674);
     }
@@ -92,7 +79,8 @@ mod tests {
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 2);
         assert_eq!(part2(input), 4);
     }
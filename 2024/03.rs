@@ -1,8 +1,9 @@
-use advent::prelude::*;
+use crate::parse::{ParseError, Source};
 
 type Input = Vec<(bool, i64, i64)>;
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let mut results = Vec::new();
     let re = regex::Regex::new(r"^mul\((\d+),(\d+)\)").unwrap();
 
@@ -16,18 +17,11 @@ fn parse_input(input: &str) -> Input {
             && let Some(captures) = re.captures(&input[i..])
         {
             let (_, [a, b]) = captures.extract();
-            results.push((enabled, a.parse().unwrap(), b.parse().unwrap()));
+            results.push((enabled, source.parse(a)?, source.parse(b)?));
         }
     }
 
-    results
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 03));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(results)
 }
 
 fn part1(input: Input) -> i64 {
@@ -41,10 +35,7 @@ fn part2(input: Input) -> i64 {
         .sum()
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 03, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -53,7 +44,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 183669043);
         assert_eq!(part2(input), 59097164);
     }
@@ -61,11 +52,13 @@ mod tests {
     #[test]
     fn examples() {
         let input =
-            parse_input("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))");
+            parse_input("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))")
+                .unwrap();
         assert_eq!(part1(input.clone()), 161);
         let input = parse_input(
             "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
-        );
+        )
+        .unwrap();
         assert_eq!(part2(input), 48);
     }
 }
@@ -1,101 +1,27 @@
-use advent::prelude::*;
+use advent_geometry::{Grid, Point2D};
 
-type Input = Vec<Vec<char>>;
+use crate::parse::{ParseError, Source};
 
-fn parse_input(input: &str) -> Input {
-    input.lines().map(|line| line.chars().collect()).collect()
-}
+type Input = Grid<char>;
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 04));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    Source::new(input).grid(input, |c, _| Ok(c))
 }
 
 fn part1(input: Input) -> i64 {
-    let mut count = 0;
-
-    let x_len = input[0].len();
-    let y_len = input.len();
-    for x in 0..x_len {
-        for y in 0..y_len {
-            let horizontal = if x + 3 < x_len {
-                format!(
-                    "{}{}{}{}",
-                    input[y][x],
-                    input[y][x + 1],
-                    input[y][x + 2],
-                    input[y][x + 3]
-                )
-            } else {
-                String::new()
-            };
-            let vertical = if y + 3 < y_len {
-                format!(
-                    "{}{}{}{}",
-                    input[y][x],
-                    input[y + 1][x],
-                    input[y + 2][x],
-                    input[y + 3][x]
-                )
-            } else {
-                String::new()
-            };
-            let diagonal = if x + 3 < x_len && y + 3 < y_len {
-                format!(
-                    "{}{}{}{}",
-                    input[y][x],
-                    input[y + 1][x + 1],
-                    input[y + 2][x + 2],
-                    input[y + 3][x + 3]
-                )
-            } else {
-                String::new()
-            };
-            let diagonal_2 = if x >= 3 && y + 3 < y_len {
-                format!(
-                    "{}{}{}{}",
-                    input[y][x],
-                    input[y + 1][x - 1],
-                    input[y + 2][x - 2],
-                    input[y + 3][x - 3]
-                )
-            } else {
-                String::new()
-            };
-
-            let v = vec![horizontal, vertical, diagonal, diagonal_2];
-            count += v.into_iter().filter(|s| s == "XMAS" || s == "SAMX").count() as i64;
-        }
-    }
+    // Each word is counted forwards and backwards, so half of the directions
+    // are enough.
+    let directions = [(1, 0), (0, 1), (1, 1), (-1, 1)].map(Point2D::from);
 
-    count
-}
-
-fn part2(input: Input) -> i64 {
     let mut count = 0;
-
-    let x_len = input[0].len();
-    let y_len = input.len();
-    for x in 0..x_len - 2 {
-        for y in 0..y_len - 2 {
-            let diagonal = format!(
-                "{}{}{}",
-                input[y][x],
-                input[y + 1][x + 1],
-                input[y + 2][x + 2]
-            );
-            let diagonal_2 = format!(
-                "{}{}{}",
-                input[y][x + 2],
-                input[y + 1][x + 1],
-                input[y + 2][x]
-            );
-
-            if (diagonal == "MAS" || diagonal == "SAM")
-                && (diagonal_2 == "MAS" || diagonal_2 == "SAM")
-            {
+    for (start, _) in input.iter() {
+        for direction in directions {
+            let word: String = input
+                .ray(start, direction)
+                .take(4)
+                .map(|(_, c)| c)
+                .collect();
+            if word == "XMAS" || word == "SAMX" {
                 count += 1;
             }
         }
@@ -104,11 +30,26 @@ fn part2(input: Input) -> i64 {
     count
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
+fn part2(input: Input) -> i64 {
+    let is_mas = |start: Point2D, direction: Point2D| {
+        let word: String = input
+            .ray(start, direction)
+            .take(3)
+            .map(|(_, c)| c)
+            .collect();
+        word == "MAS" || word == "SAM"
+    };
+
+    input
+        .points()
+        .filter(|&p| {
+            is_mas(p, Point2D::new(1, 1)) && is_mas(p + Point2D::new(2, 0), Point2D::new(-1, 1))
+        })
+        .count() as i64
 }
 
+solution!(2024 / 04, part1, part2);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,7 +57,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 2569);
         assert_eq!(part2(input), 1998);
     }
@@ -134,7 +75,8 @@ SMSMSASXSS
 SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 18);
         assert_eq!(part2(input), 9);
     }
@@ -1,5 +1,6 @@
 use advent::prelude::*;
-use std::str::FromStr;
+
+use crate::parse::{ParseError, Source};
 
 #[derive(Debug, Clone)]
 struct Input {
@@ -7,12 +8,18 @@ struct Input {
     updates: Vec<Vec<i32>>,
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
     #[derive(Debug, Copy, Clone)]
     enum Mode {
         Rules,
         Updates,
     }
+    let source = Source::new(input);
+    // Page numbers index the rules table.
+    let page = |x: &str| match source.parse::<i32>(x)? {
+        page @ 0..128 => Ok(page),
+        _ => Err(source.error(x, "page numbers go up to 127")),
+    };
     let mut mode = Mode::Rules;
     let mut data = Input {
         rules: [false; 128 * 128],
@@ -24,26 +31,20 @@ fn parse_input(input: &str) -> Input {
                 if line.is_empty() {
                     mode = Mode::Updates;
                 } else {
-                    let rule: Vec<_> = line.split("|").map(|x| i32::from_str(x).unwrap()).collect();
-                    data.rules[(rule[1] * 128 + rule[0]) as usize] = true;
+                    let mut fields = source.fields(line, line.split("|"));
+                    let before = page(fields.field()?)?;
+                    let after = page(fields.field()?)?;
+                    data.rules[(after * 128 + before) as usize] = true;
                 }
             }
             Mode::Updates => {
-                let page_nums: Vec<_> =
-                    line.split(",").map(|x| i32::from_str(x).unwrap()).collect();
+                let page_nums = line.split(",").map(page).collect::<Result<_, _>>()?;
                 data.updates.push(page_nums);
             }
         }
     }
 
-    data
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 05));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(data)
 }
 
 fn part1(input: Input) -> i32 {
@@ -81,10 +82,7 @@ fn part2(input: Input) -> i32 {
         .sum()
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 05, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -93,7 +91,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 4905);
         assert_eq!(part2(input), 6204);
     }
@@ -129,7 +127,8 @@ mod tests {
 75,97,47,61,53
 61,13,29
 97,13,75,29,47",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 143);
         assert_eq!(part2(input), 123);
     }
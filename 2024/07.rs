@@ -1,5 +1,4 @@
-use advent::prelude::*;
-use std::str::FromStr;
+use crate::parse::{ParseError, Source};
 
 #[derive(Debug, Clone)]
 struct Operation {
@@ -9,28 +8,23 @@ struct Operation {
 
 type Input = Vec<Operation>;
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .map(|line| {
-            let line_parsed: Vec<_> = line.split(": ").collect();
-            let result = i64::from_str(line_parsed[0]).unwrap();
-            let values = line_parsed[1]
+            let mut fields = source.fields(line, line.split(": "));
+            let result = fields.parse()?;
+            let values = fields
+                .field()?
                 .split(" ")
-                .map(|x| i64::from_str(x).unwrap())
-                .collect();
-            Operation { result, values }
+                .map(|x| source.parse(x))
+                .collect::<Result<_, _>>()?;
+            Ok(Operation { result, values })
         })
         .collect()
 }
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 07));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
-}
-
 fn part1(input: Input) -> i64 {
     input
         .into_iter()
@@ -93,10 +87,7 @@ fn part2(input: Input) -> i64 {
         .sum()
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 07, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -105,7 +96,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 465126289353);
         assert_eq!(part2(input), 70597497486371);
     }
@@ -122,7 +113,8 @@ mod tests {
 192: 17 8 14
 21037: 9 7 18 13
 292: 11 6 16 20",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 3749);
         assert_eq!(part2(input), 11387);
     }
@@ -1,23 +1,18 @@
 use advent::prelude::*;
+use advent_geometry::{Grid, Point2D};
 
-type Input = Vec<Vec<char>>;
+use crate::parse::{ParseError, Source};
 
-fn parse_input(input: &str) -> Input {
-    input.lines().map(|line| line.chars().collect()).collect()
-}
+type Input = Grid<char>;
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 08));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    Source::new(input).grid(input, |c, _| Ok(c))
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 struct Antenna {
     frequency: char,
-    x: i64,
-    y: i64,
+    position: Point2D,
 }
 
 fn part1(input: Input) -> usize {
@@ -29,18 +24,14 @@ fn part2(input: Input) -> usize {
 }
 
 fn calc(input: Input, with_mult: bool) -> usize {
-    let mut antennas: Vec<Antenna> = Vec::new();
-    let len_x = input[0].len() as i64;
-    let len_y = input.len() as i64;
-
-    for x in 0..len_x {
-        for y in 0..len_y {
-            let frequency = input[y as usize][x as usize];
-            if frequency != '#' && frequency != '.' {
-                antennas.push(Antenna { frequency, x, y });
-            }
-        }
-    }
+    let antennas: Vec<Antenna> = input
+        .iter()
+        .filter(|&(_, &frequency)| frequency != '#' && frequency != '.')
+        .map(|(position, &frequency)| Antenna {
+            frequency,
+            position,
+        })
+        .collect();
 
     let mut antinodes = HashSet::new();
     for i in 0..antennas.len() {
@@ -49,16 +40,15 @@ fn calc(input: Input, with_mult: bool) -> usize {
                 continue;
             }
 
-            let Antenna { x: x1, y: y1, .. } = antennas[i];
-            let Antenna { x: x2, y: y2, .. } = antennas[j];
-            let (x_diff, y_diff) = (x2 - x1, y2 - y1);
+            let first = antennas[i].position;
+            let diff = antennas[j].position - first;
 
             let mut mult = if with_mult { 0 } else { 1 };
             loop {
-                let (node_x, node_y) = (x1 - x_diff * mult, y1 - y_diff * mult);
+                let node = first - diff * mult;
 
-                if node_x >= 0 && node_x < len_x && node_y >= 0 && node_y < len_y {
-                    antinodes.insert((node_x, node_y));
+                if input.contains(node) {
+                    antinodes.insert(node);
                 } else {
                     break;
                 }
@@ -74,10 +64,7 @@ fn calc(input: Input, with_mult: bool) -> usize {
     antinodes.len()
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 08, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -86,7 +73,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 396);
         assert_eq!(part2(input), 1200);
     }
@@ -106,7 +93,8 @@ mod tests {
 ....#....A..
 .#........#.
 ...#......##",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 14);
         assert_eq!(part2(input), 34);
     }
@@ -124,7 +112,8 @@ mod tests {
 ......#...
 ..........
 ..........",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input), 2);
     }
 
@@ -141,7 +130,8 @@ mod tests {
 ..........
 ....#.....
 ..........",
-        );
+        )
+        .unwrap();
         assert_eq!(part2(input), 9);
     }
 }
@@ -1,15 +1,17 @@
 use advent::prelude::*;
 use std::ops::{Deref, DerefMut};
-use std::str::FromStr;
+
+use crate::parse::{ParseError, Source};
 
 type Input = Disk;
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let input = input.trim();
     let mut disk: Vec<_> = Vec::new();
 
-    for i in 0..input.len() {
-        let size = i64::from_str(&input[i..i + 1]).unwrap();
+    for (i, (at, c)) in input.char_indices().enumerate() {
+        let size = source.parse(&input[at..at + c.len_utf8()])?;
         if i % 2 == 0 {
             disk.push(Node::new(DiskBlock::File(i as i64 / 2), size));
         } else {
@@ -17,14 +19,7 @@ fn parse_input(input: &str) -> Input {
         }
     }
 
-    Disk(disk)
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 09));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(Disk(disk))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -128,10 +123,7 @@ fn checksum(disk: &[DiskBlock]) -> i64 {
         .sum()
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 09, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -140,14 +132,14 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 6463499258318);
         assert_eq!(part2(input), 6493634986625);
     }
 
     #[test]
     fn examples() {
-        let input = parse_input("2333133121414131402");
+        let input = parse_input("2333133121414131402").unwrap();
         assert_eq!(part1(input.clone()), 1928);
         assert_eq!(part2(input), 2858);
     }
@@ -1,89 +1,67 @@
 use advent::prelude::*;
+use advent_geometry::{Grid, Point2D};
 
-type Input = Vec<Vec<i64>>;
+use crate::parse::{ParseError, Source};
 
-fn parse_input(input: &str) -> Input {
-    input
-        .lines()
-        .map(|line| line.chars().map(|x| x as i64 - '0' as i64).collect())
-        .collect()
-}
+type Input = Grid<i64>;
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 10));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    source.grid(input, |c, at| {
+        c.to_digit(10)
+            .map(i64::from)
+            .ok_or_else(|| source.error(at, "expected a height digit"))
+    })
 }
 
 fn part1(input: Input) -> i64 {
     trailheads(&input)
-        .map(|(x, y)| reachable_peaks_num(&input, x, y, 0).len() as i64)
+        .map(|p| reachable_peaks_num(&input, p, 0).len() as i64)
         .sum()
 }
 
 fn part2(input: Input) -> i64 {
     trailheads(&input)
-        .map(|(x, y)| unique_paths_num(&input, x, y, 0))
+        .map(|p| unique_paths_num(&input, p, 0))
         .sum()
 }
 
-fn trailheads(input: &Input) -> impl Iterator<Item = (i64, i64)> + use<'_> {
-    let y_len = input.len() as i64;
-    let x_len = input[0].len() as i64;
-
-    (0..x_len)
-        .cartesian_product(0..y_len)
-        .filter(|&(x, y)| input[y as usize][x as usize] == 0)
+fn trailheads(input: &Input) -> impl Iterator<Item = Point2D> + use<'_> {
+    input
+        .iter()
+        .filter(|&(_, &height)| height == 0)
+        .map(|(p, _)| p)
 }
 
-fn reachable_peaks_num(input: &Input, x: i64, y: i64, expected: i64) -> HashSet<(i64, i64)> {
-    if invalid_input(input, x, y, expected) {
+fn reachable_peaks_num(input: &Input, p: Point2D, expected: i64) -> HashSet<Point2D> {
+    if input.get(p) != Some(&expected) {
         return HashSet::new();
     }
 
-    if input[y as usize][x as usize] == 9 {
-        return HashSet::from_iter([(x, y)]);
+    if expected == 9 {
+        return HashSet::from_iter([p]);
     }
 
-    let mut set = reachable_peaks_num(input, x - 1, y, expected + 1);
-    set.extend(reachable_peaks_num(input, x + 1, y, expected + 1));
-    set.extend(reachable_peaks_num(input, x, y - 1, expected + 1));
-    set.extend(reachable_peaks_num(input, x, y + 1, expected + 1));
-
-    set
+    p.neighbors4()
+        .flat_map(|next| reachable_peaks_num(input, next, expected + 1))
+        .collect()
 }
 
-fn unique_paths_num(input: &Input, x: i64, y: i64, expected: i64) -> i64 {
-    if invalid_input(input, x, y, expected) {
+fn unique_paths_num(input: &Input, p: Point2D, expected: i64) -> i64 {
+    if input.get(p) != Some(&expected) {
         return 0;
     }
 
-    if input[y as usize][x as usize] == 9 {
+    if expected == 9 {
         return 1;
     }
 
-    let mut count = 0;
-    count += unique_paths_num(input, x - 1, y, expected + 1);
-    count += unique_paths_num(input, x + 1, y, expected + 1);
-    count += unique_paths_num(input, x, y - 1, expected + 1);
-    count += unique_paths_num(input, x, y + 1, expected + 1);
-
-    count
-}
-
-fn invalid_input(input: &Input, x: i64, y: i64, expected: i64) -> bool {
-    x < 0
-        || y < 0
-        || x >= input[0].len() as i64
-        || y >= input.len() as i64
-        || input[y as usize][x as usize] != expected
+    p.neighbors4()
+        .map(|next| unique_paths_num(input, next, expected + 1))
+        .sum()
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 10, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -92,7 +70,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 841);
         assert_eq!(part2(input), 1875);
     }
@@ -108,7 +86,8 @@ mod tests {
 32019012
 01329801
 10456732",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 36);
         assert_eq!(part2(input), 81);
     }
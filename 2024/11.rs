@@ -1,19 +1,12 @@
 use advent::prelude::*;
 
-type Input = Vec<i64>;
+use crate::parse::{ParseError, Source};
 
-fn parse_input(input: &str) -> Input {
-    input
-        .split_whitespace()
-        .map(|x| x.parse().unwrap())
-        .collect()
-}
+type Input = Vec<i64>;
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 11));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    input.split_whitespace().map(|x| source.parse(x)).collect()
 }
 
 fn part1(input: Input) -> i64 {
@@ -56,10 +49,7 @@ fn num_after(num: i64, steps: i64, map: &mut HashMap<(i64, i64), i64>) -> i64 {
     result
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 11, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -68,16 +58,16 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 190865);
         assert_eq!(part2(input), 225404711855335);
     }
 
     #[test]
     fn examples() {
-        let input = parse_input("0 1 10 99 999");
+        let input = parse_input("0 1 10 99 999").unwrap();
         assert_eq!(blink(&input, 1), 7);
-        let input = parse_input("125 17");
+        let input = parse_input("125 17").unwrap();
         assert_eq!(blink(&input, 6), 22);
         assert_eq!(part1(input), 55312);
     }
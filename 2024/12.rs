@@ -1,25 +1,13 @@
-use advent::prelude::*;
 use std::ops::Add;
 
-type Input = Vec<Vec<char>>;
+use advent_geometry::{Grid, Point2D};
 
-fn parse_input(input: &str) -> Input {
-    let mut input: Input = input.lines().map(|line| line.chars().collect()).collect();
-    let len_x = input[0].len();
-    for line in &mut input {
-        line.insert(0, '#');
-        line.push('#');
-    }
-    input.insert(0, vec!['#'; len_x + 2]);
-    input.push(vec!['#'; len_x + 2]);
-    input
-}
+use crate::parse::{ParseError, Source};
+
+type Input = Grid<char>;
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 12));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    Source::new(input).grid(input, |c, _| Ok(c))
 }
 
 fn part1(input: Input) -> i64 {
@@ -37,14 +25,11 @@ fn part2(input: Input) -> i64 {
 }
 
 fn get_regions(input: Input) -> Vec<Region> {
-    let len_x = input[0].len();
-    let len_y = input.len();
+    let mut visited = Grid::filled(input.width(), input.height(), false);
 
-    let mut visited = vec![vec![false; len_x]; len_y];
-
-    (0..len_x)
-        .cartesian_product(0..len_y)
-        .map(|(x, y)| process_region(&input, x, y, &mut visited))
+    input
+        .points()
+        .map(|p| process_region(&input, p, &mut visited))
         .filter(|&region| region != Region::zero())
         .collect()
 }
@@ -78,60 +63,48 @@ impl Add for Region {
     }
 }
 
-fn process_region(input: &Input, x: usize, y: usize, visited: &mut Vec<Vec<bool>>) -> Region {
-    if input[y][x] == '#' || visited[y][x] {
+fn process_region(input: &Input, p: Point2D, visited: &mut Grid<bool>) -> Region {
+    if visited[p] {
         return Region::zero();
     }
-    visited[y][x] = true;
+    visited[p] = true;
 
-    let region_id = input[y][x];
+    let region_id = input[p];
+    let same = |x, y| input.get(p + Point2D::new(x, y)) == Some(&region_id);
 
     let mut region = Region {
         area: 1,
         perimeter: 4,
         sides: 4,
     };
-    let neighbours = [(x + 1, y), (x, y + 1), (x - 1, y), (x, y - 1)];
-    for (nx, ny) in neighbours.iter() {
-        if input[*ny][*nx] == region_id {
-            let neighbour = process_region(input, *nx, *ny, visited);
-            region = region + neighbour;
+    for neighbour in input.neighbors4(p) {
+        if input[neighbour] == region_id {
+            region = region + process_region(input, neighbour, visited);
             region.perimeter -= 1;
         }
     }
 
     // top side
-    if (input[y][x - 1] == region_id && input[y - 1][x - 1] != region_id)
-        || input[y - 1][x] == region_id
-    {
+    if (same(-1, 0) && !same(-1, -1)) || same(0, -1) {
         region.sides -= 1;
     }
     // bottom side
-    if (input[y][x + 1] == region_id && input[y + 1][x + 1] != region_id)
-        || input[y + 1][x] == region_id
-    {
+    if (same(1, 0) && !same(1, 1)) || same(0, 1) {
         region.sides -= 1;
     }
     // right side
-    if (input[y - 1][x] == region_id && input[y - 1][x + 1] != region_id)
-        || input[y][x + 1] == region_id
-    {
+    if (same(0, -1) && !same(1, -1)) || same(1, 0) {
         region.sides -= 1;
     }
     // left side
-    if (input[y + 1][x] == region_id && input[y + 1][x - 1] != region_id)
-        || input[y][x - 1] == region_id
-    {
+    if (same(0, 1) && !same(-1, 1)) || same(-1, 0) {
         region.sides -= 1;
     }
 
     region
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 12, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -140,7 +113,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 1344578);
         assert_eq!(part2(input), 814302);
     }
@@ -152,7 +125,8 @@ mod tests {
 BBCD
 BBCC
 EEEC",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 140);
         assert_eq!(part2(input), 80);
         let input = parse_input(
@@ -161,7 +135,8 @@ OXOXO
 OOOOO
 OXOXO
 OOOOO",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 772);
         assert_eq!(part2(input), 436);
         let input = parse_input(
@@ -175,7 +150,8 @@ VVIIICJJEE
 MIIIIIJJEE
 MIIISIJEEE
 MMMISSJEEE",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 1930);
         assert_eq!(part2(input), 1206);
     }
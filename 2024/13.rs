@@ -1,5 +1,6 @@
-use advent::prelude::*;
-use std::ops::{Add, Mul};
+use advent_geometry::Point2D;
+
+use crate::parse::{ParseError, Source};
 
 type Input = Vec<Machine>;
 
@@ -10,65 +11,27 @@ struct Machine {
     prize: Point2D,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point2D {
-    x: i64,
-    y: i64,
-}
-
-impl Point2D {
-    pub fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Add<Self> for Point2D {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl Mul<i64> for Point2D {
-    type Output = Self;
-
-    fn mul(self, rhs: i64) -> Self::Output {
-        Self {
-            x: self.x * rhs,
-            y: self.y * rhs,
-        }
-    }
-}
-
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let button_a_re = regex::Regex::new(r"^Button A: X\+(\d+), Y\+(\d+)").unwrap();
     let button_b_re = regex::Regex::new(r"^Button B: X\+(\d+), Y\+(\d+)").unwrap();
     let prize_re = regex::Regex::new(r"^Prize: X=(\d+), Y=(\d+)").unwrap();
+    let point = |re, line: Option<&str>| -> Result<_, ParseError> {
+        let line = line.ok_or_else(|| source.end_of_input())?;
+        let captures = source.captures(re, line)?;
+        let x = source.parse(captures.get(1).unwrap().as_str())?;
+        let y = source.parse(captures.get(2).unwrap().as_str())?;
+        Ok(Point2D::new(x, y))
+    };
 
     let mut machines = Input::new();
 
     let mut lines = input.lines();
     while let Some(line) = lines.next() {
-        let captures = button_a_re.captures(line).unwrap();
-        let a_x: i64 = captures.get(1).unwrap().as_str().parse().unwrap();
-        let a_y: i64 = captures.get(2).unwrap().as_str().parse().unwrap();
-
-        let captures = button_b_re.captures(lines.next().unwrap()).unwrap();
-        let b_x: i64 = captures.get(1).unwrap().as_str().parse().unwrap();
-        let b_y: i64 = captures.get(2).unwrap().as_str().parse().unwrap();
-
-        let captures = prize_re.captures(lines.next().unwrap()).unwrap();
-        let prize_x: i64 = captures.get(1).unwrap().as_str().parse().unwrap();
-        let prize_y: i64 = captures.get(2).unwrap().as_str().parse().unwrap();
-
         machines.push(Machine {
-            button_a: Point2D::new(a_x, a_y),
-            button_b: Point2D::new(b_x, b_y),
-            prize: Point2D::new(prize_x, prize_y),
+            button_a: point(&button_a_re, Some(line))?,
+            button_b: point(&button_b_re, lines.next())?,
+            prize: point(&prize_re, lines.next())?,
         });
 
         let next_line = lines.next();
@@ -77,14 +40,7 @@ fn parse_input(input: &str) -> Input {
         }
     }
 
-    machines
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 13));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(machines)
 }
 
 fn part1(input: Input) -> i64 {
@@ -116,10 +72,7 @@ fn get_price(machine: Machine) -> i64 {
     3 * n + m
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 13, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -128,7 +81,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 32026);
         assert_eq!(part2(input), 89013607072065);
     }
@@ -151,7 +104,8 @@ Prize: X=7870, Y=6450
 Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 480);
         assert_eq!(part2(input), 875318608908);
     }
@@ -1,5 +1,7 @@
 use advent::prelude::*;
-use std::ops::{Add, AddAssign};
+use advent_geometry::Point2D;
+
+use crate::parse::{ParseError, Source};
 
 type Input = Vec<Robot>;
 
@@ -9,49 +11,21 @@ struct Robot {
     velocity: Point2D,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point2D {
-    x: i64,
-    y: i64,
-}
-
-impl Point2D {
-    pub const fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Add<Self> for Point2D {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl AddAssign<Self> for Point2D {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = *self + rhs;
-    }
-}
-
 const SIZE: Point2D = Point2D::new(101, 103);
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let button_a_re = regex::Regex::new(r"^p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)$").unwrap();
 
     let mut robots = Input::new();
 
     let lines = input.lines();
     for line in lines {
-        let captures = button_a_re.captures(line).unwrap();
-        let a_x: i64 = captures.get(1).unwrap().as_str().parse().unwrap();
-        let a_y: i64 = captures.get(2).unwrap().as_str().parse().unwrap();
-        let v_x: i64 = captures.get(3).unwrap().as_str().parse().unwrap();
-        let v_y: i64 = captures.get(4).unwrap().as_str().parse().unwrap();
+        let captures = source.captures(&button_a_re, line)?;
+        let a_x: i64 = source.parse(captures.get(1).unwrap().as_str())?;
+        let a_y: i64 = source.parse(captures.get(2).unwrap().as_str())?;
+        let v_x: i64 = source.parse(captures.get(3).unwrap().as_str())?;
+        let v_y: i64 = source.parse(captures.get(4).unwrap().as_str())?;
 
         robots.push(Robot {
             position: Point2D::new(a_x, a_y),
@@ -59,14 +33,7 @@ fn parse_input(input: &str) -> Input {
         });
     }
 
-    robots
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 14));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(robots)
 }
 
 fn part1(mut input: Input) -> usize {
@@ -158,10 +125,7 @@ fn are_halves_similar(input: &Input, size: Point2D) -> bool {
     score > 100
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 14, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -170,7 +134,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 221616000);
         assert_eq!(part2(input), 7572);
     }
@@ -190,7 +154,8 @@ p=9,3 v=2,3
 p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input), 21);
     }
 }
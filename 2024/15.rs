@@ -1,19 +1,10 @@
-use advent::prelude::*;
-use std::ops::{Add, AddAssign};
+use advent_geometry::{Grid, Point2D};
 
-trait GridExt {
-    fn size(&self) -> (usize, usize);
-}
-
-impl<T> GridExt for Vec<Vec<T>> {
-    fn size(&self) -> (usize, usize) {
-        (self[0].len(), self.len())
-    }
-}
+use crate::parse::{ParseError, Source};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Input {
-    grid: Vec<Vec<Item>>,
+    grid: Grid<Item>,
     moves: Vec<Move>,
 }
 
@@ -30,8 +21,8 @@ enum Item {
 impl Item {
     fn neighbor_offset(&self) -> Point2D {
         match self {
-            Item::BoxWide1 => Point2D::new(1, 0),
-            Item::BoxWide2 => Point2D::new(-1, 0),
+            Item::BoxWide1 => Point2D::RIGHT,
+            Item::BoxWide2 => Point2D::LEFT,
             _ => panic!("invalid item"),
         }
     }
@@ -48,10 +39,10 @@ enum Move {
 impl Move {
     fn to_point(self) -> Point2D {
         match self {
-            Move::Up => Point2D::new(0, -1),
-            Move::Right => Point2D::new(1, 0),
-            Move::Down => Point2D::new(0, 1),
-            Move::Left => Point2D::new(-1, 0),
+            Move::Up => Point2D::UP,
+            Move::Right => Point2D::RIGHT,
+            Move::Down => Point2D::DOWN,
+            Move::Left => Point2D::LEFT,
         }
     }
 
@@ -67,89 +58,37 @@ impl Move {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point2D {
-    x: i64,
-    y: i64,
-}
-
-impl Point2D {
-    pub const fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Add<Self> for Point2D {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl AddAssign<Self> for Point2D {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = *self + rhs;
-    }
-}
-
-fn parse_input(input: &str) -> Input {
-    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-    enum Mode {
-        Grid,
-        Moves,
-    }
-
-    let mut mode = Mode::Grid;
-    let mut grid = Vec::new();
-    let mut moves = Vec::new();
-    for line in input.lines() {
-        match mode {
-            Mode::Grid => {
-                if line.is_empty() {
-                    mode = Mode::Moves;
-                    continue;
-                }
-
-                let row: Vec<_> = line
-                    .chars()
-                    .map(|c| match c {
-                        '.' => Item::Empty,
-                        '#' => Item::Wall,
-                        'O' => Item::Box,
-                        '@' => Item::Player,
-                        _ => panic!("invalid item: {}", c),
-                    })
-                    .collect();
-                grid.push(row);
-            }
-            Mode::Moves => {
-                let moves_line: Vec<_> = line
-                    .chars()
-                    .map(|c| match c {
-                        '^' => Move::Up,
-                        '>' => Move::Right,
-                        'v' => Move::Down,
-                        '<' => Move::Left,
-                        _ => panic!("invalid move: {}", c),
-                    })
-                    .collect();
-                moves.extend(moves_line);
-            }
-        }
-    }
-
-    Input { grid, moves }
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 15));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    let (grid, moves) = input.split_once("\n\n").unwrap_or((input, ""));
+    let mut player = None;
+    let grid = source.grid(grid, |c, at| match c {
+        '.' => Ok(Item::Empty),
+        '#' => Ok(Item::Wall),
+        'O' => Ok(Item::Box),
+        '@' if player.replace(at).is_some() => Err(source.error(at, "more than one player")),
+        '@' => Ok(Item::Player),
+        _ => Err(source.error(at, "invalid item")),
+    })?;
+    if player.is_none() {
+        return Err(source.missing("no player"));
+    }
+    let moves = moves
+        .lines()
+        .flat_map(|line| {
+            line.char_indices()
+                .map(move |(i, c)| (c, &line[i..i + c.len_utf8()]))
+        })
+        .map(|(c, at)| match c {
+            '^' => Ok(Move::Up),
+            '>' => Ok(Move::Right),
+            'v' => Ok(Move::Down),
+            '<' => Ok(Move::Left),
+            _ => Err(source.error(at, "invalid move")),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Input { grid, moves })
 }
 
 fn part1(mut input: Input) -> i64 {
@@ -164,66 +103,41 @@ fn part1(mut input: Input) -> i64 {
     calculate_score(&input.grid)
 }
 
-fn calculate_score(grid: &Vec<Vec<Item>>) -> i64 {
-    let (size_x, size_y) = grid.size();
-
-    (0..size_x)
-        .cartesian_product(0..size_y)
-        .filter(|&(x, y)| grid[y][x] == Item::Box || grid[y][x] == Item::BoxWide1)
-        .map(|(x, y)| 100 * y as i64 + x as i64)
+fn calculate_score(grid: &Grid<Item>) -> i64 {
+    grid.iter()
+        .filter(|&(_, &item)| item == Item::Box || item == Item::BoxWide1)
+        .map(|(p, _)| 100 * p.y + p.x)
         .sum()
 }
 
-fn find_player(grid: &mut [Vec<Item>]) -> Point2D {
-    for y in grid.iter_mut().enumerate() {
-        for x in y.1.iter_mut().enumerate() {
-            if *x.1 == Item::Player {
-                *x.1 = Item::Empty;
-                return Point2D::new(x.0 as i64, y.0 as i64);
-            }
-        }
-    }
-
-    panic!("player not found")
+fn find_player(grid: &mut Grid<Item>) -> Point2D {
+    let player = grid
+        .find(|&item| item == Item::Player)
+        .expect("player not found");
+    grid[player] = Item::Empty;
+    player
 }
 
 fn part2(mut input: Input) -> i64 {
-    let (_, size_y) = input.grid.size();
-    for y in 0..size_y {
-        let row = &mut input.grid[y];
-        let mut new_row = Vec::new();
-
-        for item in row.iter() {
-            match item {
-                Item::Empty => {
-                    new_row.push(Item::Empty);
-                    new_row.push(Item::Empty);
-                }
-                Item::Wall => {
-                    new_row.push(Item::Wall);
-                    new_row.push(Item::Wall);
-                }
-                Item::Box => {
-                    new_row.push(Item::BoxWide1);
-                    new_row.push(Item::BoxWide2);
-                }
-                Item::Player => {
-                    new_row.push(Item::Player);
-                    new_row.push(Item::Empty);
-                }
-                _ => {}
-            }
-        }
-
-        *row = new_row;
-    }
+    let cells = input
+        .grid
+        .iter()
+        .flat_map(|(_, item)| match item {
+            Item::Empty => [Item::Empty, Item::Empty],
+            Item::Wall => [Item::Wall, Item::Wall],
+            Item::Box => [Item::BoxWide1, Item::BoxWide2],
+            Item::Player => [Item::Player, Item::Empty],
+            Item::BoxWide1 | Item::BoxWide2 => unreachable!("the map is already wide"),
+        })
+        .collect();
+    input.grid = Grid::new(input.grid.width() * 2, input.grid.height(), cells);
 
     part1(input)
 }
 
-fn can_move(player_move: Move, grid: &mut Vec<Vec<Item>>, player: Point2D) -> bool {
+fn can_move(player_move: Move, grid: &mut Grid<Item>, player: Point2D) -> bool {
     let next_pos = player + player_move.to_point();
-    let next_item = grid[next_pos.y as usize][next_pos.x as usize];
+    let next_item = grid[next_pos];
 
     if next_item == Item::Wall {
         false
@@ -241,10 +155,10 @@ fn can_move(player_move: Move, grid: &mut Vec<Vec<Item>>, player: Point2D) -> bo
     }
 }
 
-fn handle_move(player_move: Move, grid: &mut Vec<Vec<Item>>, position: Point2D) {
+fn handle_move(player_move: Move, grid: &mut Grid<Item>, position: Point2D) {
     let next_pos = position + player_move.to_point();
-    let next_item = grid[next_pos.y as usize][next_pos.x as usize];
-    let current_item = grid[position.y as usize][position.x as usize];
+    let next_item = grid[next_pos];
+    let current_item = grid[position];
 
     if player_move.is_vertical() && (next_item == Item::BoxWide1 || next_item == Item::BoxWide2) {
         handle_move(player_move, grid, next_pos);
@@ -260,15 +174,12 @@ fn handle_move(player_move: Move, grid: &mut Vec<Vec<Item>>, position: Point2D)
     }
 }
 
-fn move_item(grid: &mut [Vec<Item>], curr: Point2D, next: Point2D, current_item: Item) {
-    grid[next.y as usize][next.x as usize] = current_item;
-    grid[curr.y as usize][curr.x as usize] = Item::Empty;
+fn move_item(grid: &mut Grid<Item>, curr: Point2D, next: Point2D, current_item: Item) {
+    grid[next] = current_item;
+    grid[curr] = Item::Empty;
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 15, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -277,7 +188,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 1446158);
         assert_eq!(part2(input), 1446175);
     }
@@ -295,7 +206,8 @@ mod tests {
 ########
 
 <^^>>>vv<v>>v<<",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 2028);
 
         let input = parse_input(
@@ -308,7 +220,8 @@ mod tests {
 #######
 
 <vv<<^^<<^^",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 908);
         assert_eq!(part2(input), 618);
 
@@ -334,7 +247,8 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 <><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 10092);
         assert_eq!(part2(input), 9021);
     }
@@ -1,7 +1,10 @@
 use advent::prelude::*;
-use std::ops::Add;
+use advent_geometry::{Grid, Point2D};
+use advent_search::{Dense, dijkstra_in};
 
-type Input = Vec<Vec<Item>>;
+use crate::parse::{ParseError, Source};
+
+type Input = Grid<Item>;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 enum Item {
@@ -11,65 +14,25 @@ enum Item {
     Empty,
 }
 
-fn parse_input(input: &str) -> Input {
-    let mut grid = Vec::new();
-    for line in input.lines() {
-        let row: Vec<_> = line
-            .chars()
-            .map(|c| match c {
-                '.' => Item::Empty,
-                '#' => Item::Wall,
-                'S' => Item::Start,
-                'E' => Item::End,
-                _ => panic!("invalid item: {}", c),
-            })
-            .collect();
-        grid.push(row);
-    }
-
-    grid
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point2D {
-    x: i64,
-    y: i64,
-}
-
-impl Point2D {
-    pub const fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    let (mut start, mut end) = (None, None);
+    let grid = source.grid(input, |c, at| match c {
+        '.' => Ok(Item::Empty),
+        '#' => Ok(Item::Wall),
+        'S' if start.replace(at).is_some() => Err(source.error(at, "more than one start")),
+        'S' => Ok(Item::Start),
+        'E' if end.replace(at).is_some() => Err(source.error(at, "more than one end")),
+        'E' => Ok(Item::End),
+        _ => Err(source.error(at, "invalid item")),
+    })?;
+    if start.is_none() {
+        return Err(source.missing("no start"));
     }
-}
-
-impl Add<Self> for Point2D {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
+    if end.is_none() {
+        return Err(source.missing("no end"));
     }
-}
-
-fn find_start(grid: &[Vec<Item>]) -> Point2D {
-    for (y, row) in grid.iter().enumerate() {
-        for (x, &item) in row.iter().enumerate() {
-            if item == Item::Start {
-                return Point2D::new(x as i64, y as i64);
-            }
-        }
-    }
-
-    panic!("start not found")
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 16));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(grid)
 }
 
 fn part1(input: Input) -> i64 {
@@ -81,151 +44,56 @@ fn part2(input: Input) -> usize {
 }
 
 fn calc(input: Input) -> (i64, usize) {
-    let position = find_start(&input);
-
-    let mut pq = BinaryHeap::new();
-    let mut distances = HashMap::new();
-    pq.push(PointInQueue {
-        distance: 0,
-        position,
-        direction: Direction::Left,
-    });
-
-    let mut end_pos = Point2D::new(0, 0);
-    let mut best_distance = i64::MAX;
-    while let Some(p) = pq.pop() {
-        let PointInQueue {
-            distance,
-            position,
-            direction,
-        } = p;
-
-        if distances.contains_key(&(position, direction)) {
-            continue;
-        }
-        distances.insert((position, direction), distance);
-
-        if input[position.y as usize][position.x as usize] == Item::End {
-            if distance < best_distance {
-                best_distance = distance;
-                end_pos = position;
-            }
-            continue;
-        }
-
-        for (dx, dy, new_direction) in [
-            (0, 1, Direction::Down),
-            (0, -1, Direction::Up),
-            (1, 0, Direction::Right),
-            (-1, 0, Direction::Left),
-        ] {
-            let new_position = Point2D::new(position.x + dx, position.y + dy);
-            if input[new_position.y as usize][new_position.x as usize] == Item::Wall {
-                continue;
-            }
-
-            let new_distance = distance + 1 + if new_direction == direction { 0 } else { 1000 };
-            pq.push(PointInQueue {
-                distance: new_distance,
-                position: new_position,
-                direction: new_direction,
-            });
-        }
-    }
-
-    let mut all_points: HashSet<Point2D> = HashSet::new();
-    for direction in &[
-        Direction::Up,
-        Direction::Down,
-        Direction::Left,
-        Direction::Right,
-    ] {
-        if let Some(&d) = distances.get(&(end_pos, *direction))
-            && d == best_distance
-        {
-            backtrack(&input, &distances, &mut all_points, end_pos, *direction, d);
-        }
-    }
+    let input = &input;
+    let start = input
+        .find(|&item| item == Item::Start)
+        .expect("start not found");
+
+    // Each cell has a state per direction.
+    let width = input.width();
+    let states = Dense::new(
+        width * input.height() * 4,
+        |&(p, d): &(Point2D, Point2D)| {
+            (p.y as usize * width + p.x as usize) * 4 + direction_index(d)
+        },
+    );
+    let search = dijkstra_in(
+        states,
+        (start, Point2D::LEFT),
+        |&(position, direction)| {
+            Point2D::ORTHOGONAL
+                .into_iter()
+                .map(move |new_direction| {
+                    let cost = if new_direction == direction { 1 } else { 1001 };
+                    ((position + new_direction, new_direction), cost)
+                })
+                .filter(|&((new_position, _), _)| {
+                    input
+                        .get(new_position)
+                        .is_some_and(|&item| item != Item::Wall)
+                })
+        },
+        |&(position, _)| input.get(position) == Some(&Item::End),
+    );
+
+    let best_distance = search.goal_cost().expect("end not reachable");
+    let all_points: HashSet<Point2D> = search
+        .on_cheapest_paths(search.goals())
+        .into_iter()
+        .map(|&(position, _)| position)
+        .collect();
 
     (best_distance, all_points.len())
 }
 
-fn backtrack(
-    input: &Input,
-    distances: &HashMap<(Point2D, Direction), i64>,
-    all_points: &mut HashSet<Point2D>,
-    pos: Point2D,
-    dir: Direction,
-    distance: i64,
-) {
-    all_points.insert(pos);
-    if input[pos.y as usize][pos.x as usize] == Item::Start {
-        return;
-    }
-
-    for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-        for new_direction in Direction::all() {
-            let new_pos = Point2D::new(pos.x - dx, pos.y - dy);
-            let new_distance = distance - 1 - if new_direction == dir { 0 } else { 1000 };
-
-            if let Some(&d) = distances.get(&(new_pos, new_direction))
-                && d == new_distance
-            {
-                backtrack(
-                    input,
-                    distances,
-                    all_points,
-                    new_pos,
-                    new_direction,
-                    new_distance,
-                );
-            }
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    Left,
-    Up,
-    Right,
-    Down,
-}
-
-impl Direction {
-    fn all() -> [Direction; 4] {
-        [
-            Direction::Left,
-            Direction::Up,
-            Direction::Right,
-            Direction::Down,
-        ]
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct PointInQueue {
-    distance: i64,
-    position: Point2D,
-    direction: Direction,
+fn direction_index(direction: Point2D) -> usize {
+    Point2D::ORTHOGONAL
+        .iter()
+        .position(|&d| d == direction)
+        .expect("not a direction")
 }
 
-impl PartialOrd for PointInQueue {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for PointInQueue {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.distance.cmp(&self.distance)
-    }
-}
-
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 16, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -234,7 +102,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 89460);
         assert_eq!(part2(input), 504);
     }
@@ -258,7 +126,8 @@ mod tests {
 #S..#.....#...#
 ###############
 ",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 7036);
         assert_eq!(part2(input), 45);
 
@@ -280,7 +149,8 @@ mod tests {
 #.#.#.#########.#
 #S#.............#
 #################",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 11048);
         assert_eq!(part2(input), 64);
     }
@@ -1,6 +1,7 @@
-use advent::prelude::*;
 use std::rc::Rc;
 
+use crate::parse::{ParseError, Source};
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Input {
     reg_a: i64,
@@ -33,7 +34,8 @@ impl Input {
     }
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let reg_a_re = regex::Regex::new(r"^Register A: (\d+)$").unwrap();
     let reg_b_re = regex::Regex::new(r"^Register B: (\d+)$").unwrap();
     let reg_c_re = regex::Regex::new(r"^Register C: (\d+)$").unwrap();
@@ -47,25 +49,25 @@ fn parse_input(input: &str) -> Input {
     };
     for line in input.lines() {
         if let Some(captures) = reg_a_re.captures(line) {
-            machine.reg_a = captures.get(1).unwrap().as_str().parse().unwrap();
+            machine.reg_a = source.parse(captures.get(1).unwrap().as_str())?;
         } else if let Some(captures) = reg_b_re.captures(line) {
-            machine.reg_b = captures.get(1).unwrap().as_str().parse().unwrap();
+            machine.reg_b = source.parse(captures.get(1).unwrap().as_str())?;
         } else if let Some(captures) = reg_c_re.captures(line) {
-            machine.reg_c = captures.get(1).unwrap().as_str().parse().unwrap();
+            machine.reg_c = source.parse(captures.get(1).unwrap().as_str())?;
         } else if let Some(captures) = prog_re.captures(line) {
             let opcodes = captures.get(1).unwrap().as_str();
-            machine.opcodes = Rc::new(opcodes.split(',').map(|x| x.parse().unwrap()).collect());
+            machine.opcodes = Rc::new(
+                opcodes
+                    .split(',')
+                    .map(|x| source.parse(x))
+                    .collect::<Result<_, _>>()?,
+            );
+        } else if !line.is_empty() {
+            return Err(source.error(line, "expected a register or the program"));
         }
     }
 
-    machine
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 17));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(machine)
 }
 
 fn part1(input: Input) -> String {
@@ -201,10 +203,7 @@ fn simulate_with(mut input: Input, output: &mut Vec<i64>, reg_a: i64) {
     simulate(input, output);
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 17, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -213,7 +212,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), "7,5,4,3,4,5,3,4,6");
         assert_eq!(part2(input), 164278899142333);
     }
@@ -226,7 +225,8 @@ Register B: 0
 Register C: 0
 
 Program: 0,1,5,4,3,0",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), "4,6,3,5,6,3,5,2,1,0");
     }
 }
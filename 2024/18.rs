@@ -1,5 +1,7 @@
-use advent::prelude::*;
-use std::ops::Add;
+use advent_geometry::{Grid, Point2D};
+use advent_search::{Dense, bfs_in};
+
+use crate::parse::{ParseError, Source};
 
 const DEFAULT_SIZE: usize = 71;
 const DEFAULT_CUTOFF: usize = 1024;
@@ -17,52 +19,23 @@ enum Item {
     Corrupted,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point2D {
-    x: i64,
-    y: i64,
-}
-
-impl Point2D {
-    pub const fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Add<Self> for Point2D {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let corrupted = input
         .lines()
         .map(|line| {
-            let mut vals = line.split(",");
-            let x = vals.next().unwrap().parse::<usize>().unwrap();
-            let y = vals.next().unwrap().parse::<usize>().unwrap();
-            Point2D::new(x as i64, y as i64)
+            let mut fields = source.fields(line, line.split(","));
+            let x: usize = fields.parse()?;
+            let y: usize = fields.parse()?;
+            Ok(Point2D::new(x as i64, y as i64))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    Input {
+    Ok(Input {
         corrupted,
         size: DEFAULT_SIZE,
         cutoff: DEFAULT_CUTOFF,
-    }
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 18));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    })
 }
 
 fn part1(input: Input) -> i64 {
@@ -70,60 +43,40 @@ fn part1(input: Input) -> i64 {
 }
 
 fn calc(corrupted: &[Point2D], size: usize) -> Option<i64> {
-    let mut grid = vec![vec![Item::Safe; size]; size];
-    for point in corrupted {
-        grid[point.y as usize][point.x as usize] = Item::Corrupted;
-    }
-
-    let mut visited = vec![vec![false; size]; size];
-
-    let mut q = VecDeque::new();
-    q.push_back((Point2D::new(0, 0), 0));
-
-    while let Some((p, dist)) = q.pop_front() {
-        if p.x < 0
-            || p.y < 0
-            || p.x >= size as i64
-            || p.y >= size as i64
-            || grid[p.y as usize][p.x as usize] == Item::Corrupted
-        {
-            continue;
-        }
-
-        if p == Point2D::new(size as i64 - 1, size as i64 - 1) {
-            return Some(dist);
-        }
-
-        if visited[p.y as usize][p.x as usize] {
-            continue;
-        }
-        visited[p.y as usize][p.x as usize] = true;
-
-        q.push_back((p + Point2D::new(1, 0), dist + 1));
-        q.push_back((p + Point2D::new(-1, 0), dist + 1));
-        q.push_back((p + Point2D::new(0, 1), dist + 1));
-        q.push_back((p + Point2D::new(0, -1), dist + 1));
+    let mut grid = Grid::filled(size, size, Item::Safe);
+    for &point in corrupted {
+        grid[point] = Item::Corrupted;
     }
 
-    None
+    let grid = &grid;
+    let end = Point2D::new(size as i64 - 1, size as i64 - 1);
+    let cells = Dense::new(size * size, |p: &Point2D| {
+        p.y as usize * size + p.x as usize
+    });
+    let search = bfs_in(
+        cells,
+        Point2D::new(0, 0),
+        |&p| {
+            p.neighbors4()
+                .filter(|&next| grid.get(next) == Some(&Item::Safe))
+        },
+        |&p| p == end,
+    );
+
+    search.goal_cost().map(|dist| dist as i64)
 }
 
 fn part2(input: Input) -> String {
-    for i in (input.cutoff + 1)..input.corrupted.len() {
-        let result = calc(&input.corrupted[0..i], input.size);
-        if result.is_none() {
-            let last = input.corrupted[i - 1];
-            return format!("{},{}", last.x, last.y);
-        }
-    }
-
-    panic!("no solution found");
+    // Once the exit is cut off it stays so, so the number of fallen bytes
+    // that first cuts it off is found by bisecting.
+    let fallen: Vec<usize> = ((input.cutoff + 1)..input.corrupted.len()).collect();
+    let blocked = fallen.partition_point(|&i| calc(&input.corrupted[0..i], input.size).is_some());
+    let i = *fallen.get(blocked).expect("no solution found");
+    let last = input.corrupted[i - 1];
+    format!("{},{}", last.x, last.y)
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 18, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -132,7 +85,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 310);
         assert_eq!(part2(input), "16,46");
     }
@@ -165,7 +118,8 @@ mod tests {
 0,5
 1,6
 2,0",
-        );
+        )
+        .unwrap();
         input.size = 7;
         input.cutoff = 12;
         assert_eq!(part1(input.clone()), 22);
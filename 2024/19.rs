@@ -1,18 +1,21 @@
 use advent::prelude::*;
 use std::hash::Hash;
 
+use crate::parse::{ParseError, Source};
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Input {
     towels: Vec<String>,
     requests: Vec<String>,
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let mut lines = input.lines();
 
     let towels = lines
         .next()
-        .unwrap()
+        .ok_or_else(|| source.end_of_input())?
         .split(", ")
         .map(str::to_string)
         .collect();
@@ -20,14 +23,7 @@ fn parse_input(input: &str) -> Input {
 
     let requests = lines.map(str::to_string).collect();
 
-    Input { towels, requests }
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 19));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(Input { towels, requests })
 }
 
 fn part1(input: Input) -> usize {
@@ -73,10 +69,7 @@ fn count_combinations_impl<'a>(
     count
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 19, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -85,7 +78,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 322);
         assert_eq!(part2(input), 715514563508258);
     }
@@ -103,7 +96,8 @@ ubwu
 bwurrg
 brgr
 bbrgwb",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 6);
         assert_eq!(part2(input), 16);
     }
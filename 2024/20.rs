@@ -1,7 +1,10 @@
 use advent::prelude::*;
-use std::ops::{Add, Sub};
+use advent_geometry::{Grid, Point2D};
+use advent_search::{Dense, bfs_in};
 
-type Input = Vec<Vec<Item>>;
+use crate::parse::{ParseError, Source};
+
+type Input = Grid<Item>;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 enum Item {
@@ -11,89 +14,27 @@ enum Item {
     Empty,
 }
 
-fn parse_input(input: &str) -> Input {
-    let mut grid = Vec::new();
-    for line in input.lines() {
-        let row: Vec<_> = line
-            .chars()
-            .map(|c| match c {
-                '.' => Item::Empty,
-                '#' => Item::Wall,
-                'S' => Item::Start,
-                'E' => Item::End,
-                _ => panic!("invalid item: {}", c),
-            })
-            .collect();
-        grid.push(row);
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    let (mut start, mut end) = (None, None);
+    let grid = source.grid(input, |c, at| match c {
+        '.' => Ok(Item::Empty),
+        '#' => Ok(Item::Wall),
+        'S' if start.replace(at).is_some() => Err(source.error(at, "more than one start")),
+        'S' => Ok(Item::Start),
+        'E' if end.replace(at).is_some() => Err(source.error(at, "more than one end")),
+        'E' => Ok(Item::End),
+        _ => Err(source.error(at, "invalid item")),
+    })?;
+    if start.is_none() {
+        return Err(source.missing("no start"));
     }
-
-    grid
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point2D {
-    x: i64,
-    y: i64,
-}
-
-impl Point2D {
-    pub const fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-
-    pub fn manhattan_distance(&self, other: Self) -> i64 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
+    if end.is_none() {
+        return Err(source.missing("no end"));
     }
+    Ok(grid)
 }
 
-impl Add<Self> for Point2D {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl Sub<Self> for Point2D {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
-    }
-}
-
-fn find_start(grid: &[Vec<Item>]) -> Point2D {
-    for (y, row) in grid.iter().enumerate() {
-        for (x, &item) in row.iter().enumerate() {
-            if item == Item::Start {
-                return Point2D::new(x as i64, y as i64);
-            }
-        }
-    }
-
-    panic!("start not found")
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 20));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
-}
-
-const DIRECTIONS: &[Point2D; 4] = &[
-    Point2D::new(0, 1),
-    Point2D::new(1, 0),
-    Point2D::new(0, -1),
-    Point2D::new(-1, 0),
-];
-
 fn part1(input: Input) -> i64 {
     calc(input, 2, 100)
 }
@@ -103,57 +44,45 @@ fn part2(input: Input) -> i64 {
 }
 
 fn calc(input: Input, max_distance: i64, min_save: i64) -> i64 {
-    let position = find_start(&input);
-
-    let mut q = VecDeque::new();
-    q.push_back(PointInQueue {
-        distance: 0,
-        position,
+    let input = &input;
+    let start = input
+        .find(|&item| item == Item::Start)
+        .expect("start not found");
+
+    let width = input.width();
+    let cells = Dense::new(width * input.height(), |p: &Point2D| {
+        p.y as usize * width + p.x as usize
     });
+    // The race is over at the end, so the track is not followed past it.
+    let distances = bfs_in(
+        cells,
+        start,
+        |&position| {
+            let end = input.get(position) == Some(&Item::End);
+            position.neighbors4().filter(move |&new_position| {
+                !end && input
+                    .get(new_position)
+                    .is_some_and(|&item| item != Item::Wall)
+            })
+        },
+        |_| false,
+    );
 
-    let mut distances = HashMap::new();
-    while let Some(PointInQueue { distance, position }) = q.pop_front() {
-        if distances.contains_key(&position) {
-            continue;
-        }
-        distances.insert(position, distance);
-
-        if input[position.y as usize][position.x as usize] == Item::End {
-            continue;
-        }
-
-        for &direction in DIRECTIONS {
-            let new_position = position + direction;
-            if input[new_position.y as usize][new_position.x as usize] == Item::Wall {
-                continue;
-            }
-            q.push_back(PointInQueue {
-                distance: distance + 1,
-                position: new_position,
-            });
-        }
-    }
-
-    let size_y = input.len() as i64;
-    let size_x = input[0].len() as i64;
     let mut count = 0;
-
-    let it = (0..size_y).cartesian_product(0..size_x);
-    for ((y, x), (dy, dx)) in it.cartesian_product(
+    for (before_position, (dy, dx)) in input.points().cartesian_product(
         (-max_distance..=max_distance).cartesian_product(-max_distance..=max_distance),
     ) {
-        let before_position = Point2D::new(x, y);
-        let after_position = Point2D::new(x + dx, y + dy);
-        let dist = after_position.manhattan_distance(before_position);
+        let after_position = before_position + Point2D::new(dx, dy);
+        let dist = after_position.manhattan(before_position);
 
-        if dist > max_distance {
+        if dist > max_distance || !input.contains(after_position) {
             continue;
         }
 
-        if let (Some(&before_dist), Some(&after_dist)) = (
-            distances.get(&before_position),
-            distances.get(&after_position),
-        ) && after_dist - before_dist >= min_save + dist
+        if let (Some(before_dist), Some(after_dist)) = (
+            distances.cost(&before_position),
+            distances.cost(&after_position),
+        ) && after_dist as i64 - before_dist as i64 >= min_save + dist
         {
             count += 1;
         }
@@ -162,28 +91,7 @@ fn calc(input: Input, max_distance: i64, min_save: i64) -> i64 {
     count
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct PointInQueue {
-    distance: i64,
-    position: Point2D,
-}
-
-impl PartialOrd for PointInQueue {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for PointInQueue {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.distance.cmp(&self.distance)
-    }
-}
-
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 20, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -192,7 +100,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 1485);
         assert_eq!(part2(input), 1027501);
     }
@@ -215,7 +123,8 @@ mod tests {
 #.#.#.#.#.#.###
 #...#...#...###
 ###############",
-        );
+        )
+        .unwrap();
         assert_eq!(calc(input.clone(), 2, 60), 1);
         assert_eq!(calc(input, 20, 70), 41);
     }
@@ -1,18 +1,24 @@
 use advent::prelude::*;
-use std::ops::{Add, Sub};
+use advent_geometry::Point2D;
 use std::str::FromStr;
 
-type Input = Vec<String>;
+use crate::parse::{ParseError, Source};
 
-fn parse_input(input: &str) -> Input {
-    input.lines().map(str::to_owned).collect()
-}
+type Input = Vec<String>;
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 21));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    input
+        .lines()
+        .map(|line| {
+            // The complexity takes the numeric part of the code.
+            let digits = line
+                .strip_suffix('A')
+                .ok_or_else(|| source.error(line, "expected a code ending in `A`"))?;
+            source.parse::<i64>(digits)?;
+            Ok(line.to_owned())
+        })
+        .collect()
 }
 
 fn part1(input: Input) -> i64 {
@@ -29,48 +35,6 @@ fn part2(input: Input) -> i64 {
         .sum()
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point2D {
-    x: i64,
-    y: i64,
-}
-
-impl Point2D {
-    pub const fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-
-    pub const fn with_x(&self) -> Point2D {
-        Self::new(self.x, 0)
-    }
-
-    pub const fn with_y(&self) -> Point2D {
-        Self::new(0, self.y)
-    }
-}
-
-impl Add<Self> for Point2D {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl Sub<Self> for Point2D {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
-    }
-}
-
 fn calc_sequence(line: &str, num_keyboards: usize) -> i64 {
     let mut sequences = numeric_to_directional_keypad(line);
     retain_shortest(&mut sequences);
@@ -102,20 +66,21 @@ fn calc(seq: String, num_keyboards: usize, mem: &mut HashMap<(String, usize), i6
     for key in seq.chars() {
         let new_pos = DirectionalKeypad.get_pos(key);
         let diff = new_pos - pos;
+        let (diff_x, diff_y) = (Point2D::new(diff.x, 0), Point2D::new(0, diff.y));
 
-        let x_seq = diff_to_seq(diff.with_x());
-        let y_seq = diff_to_seq(diff.with_y());
+        let x_seq = diff_to_seq(diff_x);
+        let y_seq = diff_to_seq(diff_y);
 
         let mut min = i64::MAX;
         if x_seq.is_empty() || y_seq.is_empty() {
             let seq = format!("{}{}A", x_seq, y_seq);
             min = calc(seq, num_keyboards - 1, mem);
         } else {
-            if pos + diff.with_x() != DirectionalKeypad.get_banned() {
+            if pos + diff_x != DirectionalKeypad.get_banned() {
                 let seq = format!("{}{}A", x_seq, y_seq);
                 min = min.min(calc(seq, num_keyboards - 1, mem));
             }
-            if pos + diff.with_y() != DirectionalKeypad.get_banned() {
+            if pos + diff_y != DirectionalKeypad.get_banned() {
                 let seq = format!("{}{}A", y_seq, x_seq);
                 min = min.min(calc(seq, num_keyboards - 1, mem));
             }
@@ -152,19 +117,20 @@ fn calc_sequence_inner<K: Keypad>(
     let key = line.chars().next().unwrap();
     let new_pos = keypad.get_pos(key);
     let diff = new_pos - pos;
+    let (diff_x, diff_y) = (Point2D::new(diff.x, 0), Point2D::new(0, diff.y));
 
-    let x_seq = diff_to_seq(diff.with_x());
-    let y_seq = diff_to_seq(diff.with_y());
+    let x_seq = diff_to_seq(diff_x);
+    let y_seq = diff_to_seq(diff_y);
 
     if x_seq.is_empty() || y_seq.is_empty() {
         let seq = format!("{}{}{}A", cur_seq, x_seq, y_seq);
         calc_sequence_inner(&line[1..], new_pos, seq, sequences, keypad);
     } else {
-        if pos + diff.with_x() != keypad.get_banned() {
+        if pos + diff_x != keypad.get_banned() {
             let seq = format!("{}{}{}A", cur_seq, x_seq, y_seq);
             calc_sequence_inner(&line[1..], new_pos, seq, sequences, keypad);
         }
-        if pos + diff.with_y() != keypad.get_banned() {
+        if pos + diff_y != keypad.get_banned() {
             let seq = format!("{}{}{}A", cur_seq, y_seq, x_seq);
             calc_sequence_inner(&line[1..], new_pos, seq, sequences, keypad);
         }
@@ -234,10 +200,7 @@ impl Keypad for DirectionalKeypad {
     }
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 21, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -246,7 +209,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 157230);
         assert_eq!(part2(input), 195969155897936);
     }
@@ -259,7 +222,8 @@ mod tests {
 179A
 456A
 379A",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 126384);
         assert_eq!(part2(input), 154115708116294);
     }
@@ -1,19 +1,14 @@
-use advent::prelude::*;
 use itertools::iproduct;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 
-type Input = Vec<i64>;
+use crate::parse::{ParseError, Source};
 
-fn parse_input(input: &str) -> Input {
-    input.lines().map(|line| line.parse().unwrap()).collect()
-}
+type Input = Vec<i64>;
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 22));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    input.lines().map(|line| source.parse(line)).collect()
 }
 
 fn part1(input: Input) -> i64 {
@@ -91,10 +86,7 @@ fn calc(num: i64) -> i64 {
     (num ^ (num * 2048)) % 16777216
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 22, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -103,7 +95,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 14392541715);
         assert_eq!(part2(input), 1628);
     }
@@ -115,7 +107,8 @@ mod tests {
 10
 100
 2024",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 37327623);
 
         let input = parse_input(
@@ -123,7 +116,8 @@ mod tests {
 2
 3
 2024",
-        );
+        )
+        .unwrap();
         assert_eq!(part2(input), 23);
     }
 }
@@ -1,13 +1,16 @@
 use advent::prelude::*;
 
+use crate::parse::{ParseError, Source};
+
 type Input = HashMap<String, HashSet<String>>;
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let mut result = HashMap::new();
     for line in input.lines() {
-        let mut parts = line.split("-");
-        let first = parts.next().unwrap().to_owned();
-        let second = parts.next().unwrap().to_owned();
+        let mut parts = source.fields(line, line.split("-"));
+        let first = parts.field()?.to_owned();
+        let second = parts.field()?.to_owned();
 
         result
             .entry(first.clone())
@@ -19,14 +22,7 @@ fn parse_input(input: &str) -> Input {
             .insert(first.clone());
     }
 
-    result
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 23));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(result)
 }
 
 fn part1(input: Input) -> i64 {
@@ -96,10 +92,7 @@ fn should_add_to_clique<'a>(input: &'a Input, clique: &[&'a str], node: &'a str)
     true
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 23, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -108,7 +101,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 1306);
         assert_eq!(part2(input), "bd,dk,ir,ko,lk,nn,ob,pt,te,tl,uh,wj,yl");
     }
@@ -148,7 +141,8 @@ co-tc
 wh-qp
 tb-vc
 td-yn",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 7);
         assert_eq!(part2(input), "co,de,ka,ta");
     }
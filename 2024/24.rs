@@ -1,5 +1,7 @@
 use advent::prelude::*;
 
+use crate::parse::{ParseError, Source};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Input {
     inputs: HashMap<String, bool>,
@@ -31,12 +33,13 @@ impl Operator {
     }
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
     enum Mode {
         Inputs,
         Operations,
     }
 
+    let source = Source::new(input);
     let mut mode = Mode::Inputs;
     let mut result = Input {
         inputs: HashMap::new(),
@@ -49,26 +52,25 @@ fn parse_input(input: &str) -> Input {
             continue;
         }
 
+        let mut it = source.fields(line, line.split_whitespace());
         match mode {
             Mode::Inputs => {
-                let mut it = line.split_whitespace();
-                let name = it.next().unwrap().trim_end_matches(':').to_string();
-                let value = it.next().unwrap().parse::<i32>().unwrap();
+                let name = it.field()?.trim_end_matches(':').to_string();
+                let value = it.parse::<i32>()?;
                 result.inputs.insert(name, value != 0);
             }
             Mode::Operations => {
-                let mut it = line.split_whitespace();
-                let left = it.next().unwrap().to_string();
-                let op = it.next().unwrap();
-                let right = it.next().unwrap().to_string();
-                let _ = it.next();
-                let output = it.next().unwrap().to_string();
+                let left = it.field()?.to_string();
+                let op = it.field()?;
+                let right = it.field()?.to_string();
+                let _ = it.field()?;
+                let output = it.field()?.to_string();
 
                 let op = match op {
                     "AND" => Operator::And,
                     "OR" => Operator::Or,
                     "XOR" => Operator::Xor,
-                    _ => unreachable!(),
+                    _ => return Err(source.error(op, "invalid operator")),
                 };
 
                 result.gates.push(Gate {
@@ -81,14 +83,7 @@ fn parse_input(input: &str) -> Input {
         }
     }
 
-    result
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 24));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(result)
 }
 
 fn part1(input: Input) -> i64 {
@@ -285,10 +280,7 @@ fn rename_gate(input: &mut Input, old_name: &str, new_name: &str) {
     }
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 24, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -297,7 +289,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 55730288838374);
     }
 
@@ -314,7 +306,8 @@ y02: 0
 x00 AND y00 -> z00
 x01 XOR y01 -> z01
 x02 OR y02 -> z02",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 4);
     }
 }
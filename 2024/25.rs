@@ -1,21 +1,33 @@
 use advent::prelude::*;
 
+use crate::parse::{ParseError, Source};
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Input {
     locks: Vec<[i64; 5]>,
     keys: Vec<[i64; 5]>,
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let mut data = Input {
         locks: Vec::new(),
         keys: Vec::new(),
     };
 
-    let mut grid: Vec<String> = Vec::new();
-    let input = format!("{}\n\n", input.trim());
-    for line in input.lines() {
+    let mut grid: Vec<&str> = Vec::new();
+    for line in input.trim().lines().chain([""]) {
         if line.is_empty() {
+            if let Some(row) = grid.iter().find(|row| row.len() != 5) {
+                return Err(source.error(row, "expected 5 columns"));
+            }
+            if grid.len() != 7 {
+                let last = grid
+                    .last()
+                    .ok_or_else(|| source.error(line, "expected a schematic"))?;
+                return Err(source.error(last, "expected 7 rows"));
+            }
+
             let is_lock = grid[0] == "#####";
             if !is_lock {
                 grid.reverse();
@@ -38,18 +50,11 @@ fn parse_input(input: &str) -> Input {
 
             grid.clear();
         } else {
-            grid.push(line.to_owned());
+            grid.push(line);
         }
     }
 
-    data
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2024 / 25));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(data)
 }
 
 fn part1(input: Input) -> usize {
@@ -72,10 +77,7 @@ fn part2(_input: Input) -> &'static str {
     "no part 2 on day 25!"
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2024 / 25, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -84,7 +86,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input), 3451);
     }
 
@@ -130,7 +132,8 @@ mod tests {
 #.#..
 #.#.#
 #####",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input), 3);
     }
 }
@@ -1,24 +1,25 @@
-use advent::prelude::*;
+use crate::parse::{ParseError, Source};
 
 type Input = Vec<i64>;
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .map(|line| {
-            let num = &line[1..].parse::<i64>().unwrap();
-            if line.starts_with('L') { -num } else { *num }
+            let (direction, num) = line
+                .split_at_checked(1)
+                .ok_or_else(|| source.error(line, "expected a rotation"))?;
+            let num: i64 = source.parse(num)?;
+            match direction {
+                "L" => Ok(-num),
+                "R" => Ok(num),
+                _ => Err(source.error(direction, "expected `L` or `R`")),
+            }
         })
         .collect()
 }
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 01));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
-}
-
 fn part1(input: Input) -> i64 {
     let mut rot = 50;
     let mut count = 0;
@@ -57,10 +58,7 @@ fn part2(input: Input) -> i64 {
     count
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 01, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -69,7 +67,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 1059);
         assert_eq!(part2(input), 6305);
     }
@@ -87,7 +85,8 @@ L1
 L99
 R14
 L82",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 3);
         assert_eq!(part2(input), 6);
     }
@@ -1,26 +1,20 @@
-use advent::prelude::*;
+use crate::parse::{ParseError, Source};
 
 type Input = Vec<(i64, i64)>;
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     input
         .split(",")
         .map(|range| {
-            let parts: Vec<&str> = range.split('-').collect();
-            let start: i64 = parts[0].trim().parse().unwrap();
-            let end: i64 = parts[1].trim().parse().unwrap();
-            (start, end)
+            let mut parts = source.fields(range, range.split('-'));
+            let start: i64 = source.parse(parts.field()?.trim())?;
+            let end: i64 = source.parse(parts.field()?.trim())?;
+            Ok((start, end))
         })
         .collect()
 }
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 02));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
-}
-
 fn part1(input: Input) -> i64 {
     check_repeated_impl(input, Policy::DivideInHalf)
 }
@@ -79,10 +73,7 @@ enum Policy {
     DivideAny,
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 02, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -91,7 +82,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 24157613387);
         assert_eq!(part2(input), 33832678380);
     }
@@ -100,7 +91,7 @@ mod tests {
     fn examples() {
         let input = parse_input(
             "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124",
-        );
+        ).unwrap();
         assert_eq!(part1(input.clone()), 1227775554);
         assert_eq!(part2(input), 4174379265);
     }
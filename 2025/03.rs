@@ -1,27 +1,40 @@
-use advent::prelude::*;
+use crate::parse::{ParseError, Source};
 
 type Input = Vec<Vec<u8>>;
 
-fn parse_input(input: &str) -> Input {
+/// The digits part 1 picks from a bank, and so the fewest a bank can have.
+const PART1_DIGITS: usize = 2;
+
+/// The digits part 2 picks from a bank.
+const PART2_DIGITS: usize = 12;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
-        .map(|line| line.as_bytes().to_owned())
+        .map(
+            |line| match line.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+                Some((i, c)) => Err(source.error(&line[i..i + c.len_utf8()], "expected a digit")),
+                None if line.len() < PART1_DIGITS => Err(source.error(
+                    line,
+                    format_args!("expected at least {PART1_DIGITS} digits"),
+                )),
+                None => Ok(line.as_bytes().to_owned()),
+            },
+        )
         .collect()
 }
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 03));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
-}
-
 fn part1(input: Input) -> i64 {
-    solve(input, 2)
+    solve(input, PART1_DIGITS)
 }
 
 fn part2(input: Input) -> i64 {
-    solve(input, 12)
+    assert!(
+        input.iter().all(|bank| bank.len() >= PART2_DIGITS),
+        "part 2 needs banks of at least {PART2_DIGITS} digits"
+    );
+    solve(input, PART2_DIGITS)
 }
 
 #[inline(always)]
@@ -62,10 +75,7 @@ fn get_max(s: &[u8], start: usize, end: usize) -> usize {
     max_char_index
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 03, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -74,7 +84,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 17493);
         assert_eq!(part2(input), 173685428989126);
     }
@@ -86,7 +96,8 @@ mod tests {
 811111111111119
 234234234234278
 818181911112111",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 357);
         assert_eq!(part2(input), 3121910778619);
     }
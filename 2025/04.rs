@@ -1,16 +1,11 @@
-use advent::prelude::*;
+use advent_geometry::Grid;
 
-type Input = Vec<Vec<u8>>;
+use crate::parse::{ParseError, Source};
 
-fn parse_input(input: &str) -> Input {
-    input.lines().map(|x| x.as_bytes().to_owned()).collect()
-}
+type Input = Grid<char>;
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 04));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    Source::new(input).grid(input, |c, _| Ok(c))
 }
 
 fn part1(mut input: Input) -> i64 {
@@ -32,57 +27,20 @@ fn part2(mut input: Input) -> i64 {
 }
 
 fn remove_accessible(input: &mut Input) -> i64 {
-    let mut counter = 0;
-    let mut to_remove = vec![];
-
-    for y in 0..input.len() {
-        for x in 0..input[0].len() {
-            if input[y].get(x) != Some(&b'@') {
-                continue;
-            }
-
-            let mut rolls = 0;
-
-            for dy in -1..=1 {
-                for dx in -1..=1 {
-                    if (dx, dy) == (0, 0) {
-                        continue;
-                    }
-
-                    let x = x as i32 + dx;
-                    let y = y as i32 + dy;
-
-                    let Some(row) = input.get_mut(y as usize) else {
-                        continue;
-                    };
-                    let Some(c) = row.get(x as usize) else {
-                        continue;
-                    };
-
-                    if *c == b'@' {
-                        rolls += 1;
-                    }
-                }
-            }
-
-            if rolls < 4 {
-                counter += 1;
-                to_remove.push((x, y));
-            }
-        }
+    let to_remove: Vec<_> = input
+        .iter()
+        .filter(|&(p, &c)| c == '@' && input.neighbors8(p).filter(|&n| input[n] == '@').count() < 4)
+        .map(|(p, _)| p)
+        .collect();
+
+    for &p in &to_remove {
+        input[p] = '.';
     }
 
-    for (x, y) in to_remove {
-        *input.get_mut(y).unwrap().get_mut(x).unwrap() = b'.';
-    }
-
-    counter
+    to_remove.len() as i64
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 04, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -91,7 +49,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 1356);
         assert_eq!(part2(input), 8713);
     }
@@ -109,7 +67,8 @@ mod tests {
 @.@@@.@@@@
 .@@@@@@@@.
 @.@.@@@.@.",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 13);
         assert_eq!(part2(input), 43);
     }
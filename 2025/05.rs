@@ -1,4 +1,4 @@
-use advent::prelude::*;
+use crate::parse::{ParseError, Source};
 
 #[derive(Clone)]
 struct Input {
@@ -6,39 +6,33 @@ struct Input {
     available: Vec<i64>,
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let mut sections = input.split("\n\n");
 
     let fresh_ranges = sections
         .next()
-        .unwrap()
+        .ok_or_else(|| source.end_of_input())?
         .lines()
         .map(|line| {
-            let mut parts = line.split('-');
-            let start: i64 = parts.next().unwrap().trim().parse().unwrap();
-            let end: i64 = parts.next().unwrap().trim().parse().unwrap();
-            (start, end)
+            let mut parts = source.fields(line, line.split('-'));
+            let start: i64 = source.parse(parts.field()?.trim())?;
+            let end: i64 = source.parse(parts.field()?.trim())?;
+            Ok((start, end))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let available = sections
         .next()
-        .unwrap()
+        .ok_or_else(|| source.end_of_input())?
         .lines()
-        .map(|line| line.trim().parse().unwrap())
-        .collect();
+        .map(|line| source.parse(line.trim()))
+        .collect::<Result<_, _>>()?;
 
-    Input {
+    Ok(Input {
         fresh_ranges,
         available,
-    }
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 05));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    })
 }
 
 fn part1(input: Input) -> i64 {
@@ -79,10 +73,7 @@ fn part2(mut input: Input) -> i64 {
         .sum()
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 05, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -91,7 +82,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 643);
         assert_eq!(part2(input), 342018167474526);
     }
@@ -110,7 +101,8 @@ mod tests {
 11
 17
 32",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 3);
         assert_eq!(part2(input), 14);
     }
@@ -1,4 +1,4 @@
-use advent::prelude::*;
+use crate::parse::{ParseError, Source};
 
 #[derive(Debug, Clone)]
 struct Input {
@@ -30,13 +30,17 @@ enum MathOp {
     Multiply,
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let lines: Vec<_> = input.lines().collect();
-    let mut columns = prepare_columns(&lines);
+    let mut columns = prepare_columns(source, &lines)?;
 
     for line in lines.iter().take(lines.len() - 1) {
         for (col_idx, part) in line.split_whitespace().enumerate() {
-            columns[col_idx].numbers.push(part.parse::<i64>().unwrap());
+            let column = columns
+                .get_mut(col_idx)
+                .ok_or_else(|| source.error(part, "number without an operator"))?;
+            column.numbers.push(source.parse(part)?);
         }
     }
 
@@ -45,49 +49,53 @@ fn parse_input(input: &str) -> Input {
         for x in 0..col.width {
             let mut num = String::new();
             for idx in 0..lines.len() - 1 {
-                num.push(lines[idx].as_bytes()[offset + x] as char);
+                // Editors may strip the trailing spaces of shorter lines.
+                let digit = lines[idx].as_bytes().get(offset + x).unwrap_or(&b' ');
+                num.push(*digit as char);
             }
-            col.numbers_vert.push(num.trim().parse::<i64>().unwrap());
+            let num = num.trim();
+            let number = num.parse().map_err(|err| {
+                let top = lines[0].get(offset + x..offset + x + 1).unwrap_or_default();
+                source.error(top, format!("cannot parse the column `{num}`: {err}"))
+            })?;
+            col.numbers_vert.push(number);
         }
 
         offset += col.width + 1;
     }
 
-    Input { columns }
+    Ok(Input { columns })
 }
 
-fn prepare_columns(lines: &Vec<&str>) -> Vec<Column> {
+fn prepare_columns(source: Source<'_>, lines: &[&str]) -> Result<Vec<Column>, ParseError> {
     let mut columns = Vec::new();
 
     let mut width = 0;
     let mut op = None;
-    for ch in lines.last().unwrap().chars() {
+    let operators = lines.last().ok_or_else(|| source.end_of_input())?;
+    for (i, ch) in operators.char_indices() {
         if ch.is_whitespace() {
             width += 1;
         } else {
-            if op.is_some() {
-                columns.push(Column::new(width, op.take().unwrap()));
+            if let Some(op) = op.take() {
+                columns.push(Column::new(width, op));
                 width = 0;
-                op = None;
             }
 
             if ch == '+' {
                 op = Some(MathOp::Add);
             } else if ch == '*' {
                 op = Some(MathOp::Multiply);
+            } else {
+                let at = &operators[i..i + ch.len_utf8()];
+                return Err(source.error(at, "expected `+` or `*`"));
             }
         }
     }
-    columns.push(Column::new(width + 1, op.take().unwrap()));
+    let op = op.ok_or_else(|| source.error(operators, "expected an operator"))?;
+    columns.push(Column::new(width + 1, op));
 
-    columns
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 06));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(columns)
 }
 
 fn part1(input: Input) -> i64 {
@@ -109,10 +117,7 @@ fn solve_part(input: Input, numbers: fn(&Column) -> &[i64]) -> i64 {
         .sum()
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 06, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -121,7 +126,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 4405895212738);
         assert_eq!(part2(input), 7450962489289);
     }
@@ -133,7 +138,8 @@ mod tests {
             " 45 64  387 23 \n",
             "  6 98  215 314\n",
             "*   +   *   +  "
-        ));
+        ))
+        .unwrap();
         assert_eq!(part1(input.clone()), 4277556);
         assert_eq!(part2(input), 3263827);
     }
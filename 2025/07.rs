@@ -1,16 +1,16 @@
 use advent::prelude::*;
+use advent_geometry::{Grid, Point2D};
 
-type Input = Vec<Vec<u8>>;
+use crate::parse::{ParseError, Source};
 
-fn parse_input(input: &str) -> Input {
-    input.lines().map(|s| s.as_bytes().to_vec()).collect()
-}
+type Input = Grid<char>;
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 07));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    source.grid(input, |c, at| match c {
+        '.' | '^' | 'S' => Ok(c),
+        _ => Err(source.error(at, "invalid cell")),
+    })
 }
 
 fn part1(input: Input) -> i64 {
@@ -28,15 +28,7 @@ struct Solution {
 }
 
 fn solve(input: Input) -> Solution {
-    let start_pos = input
-        .iter()
-        .enumerate()
-        .find_map(|(y, row)| {
-            row.iter()
-                .position(|&c| c == b'S')
-                .map(|x| (x as i64, y as i64))
-        })
-        .unwrap();
+    let start_pos = input.find(|&c| c == 'S').unwrap();
 
     let mut beams = HashMap::new();
     beams.insert(start_pos, 1);
@@ -45,23 +37,23 @@ fn solve(input: Input) -> Solution {
     loop {
         let mut new_beams = HashMap::new();
 
-        for (&(x, y), &ways) in &beams {
-            let y = y + 1;
-            if y >= input.len() as i64 {
+        for (&position, &ways) in &beams {
+            let position = position + Point2D::DOWN;
+            if position.y >= input.height() as i64 {
                 return Solution {
                     splits,
                     paths: beams.values().sum(),
                 };
             }
 
-            match input[y as usize][x as usize] {
-                b'.' => {
-                    *new_beams.entry((x, y)).or_default() += ways;
+            match input[position] {
+                '.' => {
+                    *new_beams.entry(position).or_default() += ways;
                 }
-                b'^' => {
+                '^' => {
                     splits += 1;
-                    *new_beams.entry((x - 1, y)).or_default() += ways;
-                    *new_beams.entry((x + 1, y)).or_default() += ways;
+                    *new_beams.entry(position + Point2D::LEFT).or_default() += ways;
+                    *new_beams.entry(position + Point2D::RIGHT).or_default() += ways;
                 }
                 _ => {
                     panic!("invalid cell")
@@ -73,10 +65,7 @@ fn solve(input: Input) -> Solution {
     }
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 07, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -85,7 +74,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 1649);
         assert_eq!(part2(input), 16937871060075);
     }
@@ -110,7 +99,8 @@ mod tests {
 .^.^.^.^.^...^.
 ...............
 ",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 21);
         assert_eq!(part2(input), 40);
     }
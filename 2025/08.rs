@@ -1,45 +1,35 @@
-use advent::prelude::*;
+use advent_geometry::Point3D;
 
-type Input = Vec<Point>;
+use crate::parse::{ParseError, Source};
+
+type Input = Vec<JunctionBox>;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point {
-    x: i64,
-    y: i64,
-    z: i64,
+struct JunctionBox {
+    position: Point3D,
     repr: usize,
     size: usize,
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .enumerate()
         .map(|(idx, line)| {
-            let mut parts = line
-                .split(',')
-                .map(|val| val.trim().parse::<i64>().unwrap());
-            let x: i64 = parts.next().unwrap();
-            let y: i64 = parts.next().unwrap();
-            let z: i64 = parts.next().unwrap();
-            Point {
-                x,
-                y,
-                z,
+            let mut parts = source.fields(line, line.split(',').map(str::trim));
+            let x: i64 = parts.parse()?;
+            let y: i64 = parts.parse()?;
+            let z: i64 = parts.parse()?;
+            Ok(JunctionBox {
+                position: Point3D::new(x, y, z),
                 repr: idx,
                 size: 1,
-            }
+            })
         })
         .collect()
 }
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 08));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
-}
-
 fn part1(input: Input) -> i64 {
     solve(input, Policy::NFirst(1000))
 }
@@ -60,9 +50,8 @@ fn solve(mut input: Input, policy: Policy) -> i64 {
 
     for i in 0..input.len() {
         for j in i + 1..input.len() {
-            let dist = (input[i].x - input[j].x).pow(2)
-                + (input[i].y - input[j].y).pow(2)
-                + (input[i].z - input[j].z).pow(2);
+            let offset = input[i].position - input[j].position;
+            let dist = offset.dot(offset);
             dists.push((dist, i, j));
         }
     }
@@ -90,7 +79,7 @@ fn solve(mut input: Input, policy: Policy) -> i64 {
             let size_j = input[repr_j].size;
 
             if policy == Policy::FinalMerge && size_i + size_j == input.len() {
-                return input[i].x * input[j].x;
+                return input[i].position.x * input[j].position.x;
             }
 
             if size_i < size_j {
@@ -107,10 +96,7 @@ fn solve(mut input: Input, policy: Policy) -> i64 {
     input.iter().take(3).map(|p| p.size).product::<usize>() as i64
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 08, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -119,7 +105,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 79560);
         assert_eq!(part2(input), 31182420);
     }
@@ -148,7 +134,8 @@ mod tests {
 984,92,344
 425,690,689
 ",
-        );
+        )
+        .unwrap();
         assert_eq!(solve(input.clone(), Policy::NFirst(10)), 40);
         assert_eq!(part2(input), 25272);
     }
@@ -1,36 +1,30 @@
 use advent::prelude::*;
 
-type Input = Vec<(i64, i64)>;
+use advent_geometry::Point2D;
 
-fn parse_input(input: &str) -> Input {
+use crate::parse::{ParseError, Source};
+
+type Input = Vec<Point2D>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .map(|line| {
-            let numbers = line.split(",");
-            let mut iter = numbers.into_iter();
-            let x: i64 = iter.next().unwrap().trim().parse().unwrap();
-            let y: i64 = iter.next().unwrap().trim().parse().unwrap();
-            (x, y)
+            let mut numbers = source.fields(line, line.split(",").map(str::trim));
+            let x: i64 = numbers.parse()?;
+            let y: i64 = numbers.parse()?;
+            Ok(Point2D::new(x, y))
         })
         .collect()
 }
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 09));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
-}
-
 fn part1(input: Input) -> i64 {
     let mut max_size = 0;
 
     for i in 0..input.len() {
         for j in i + 1..input.len() {
-            let (x1, y1) = input[i];
-            let (x2, y2) = input[j];
-
-            let size = ((x2 - x1).abs() + 1) * ((y2 - y1).abs() + 1);
+            let size = area(input[i], input[j]);
             if size > max_size {
                 max_size = max_size.max(size);
             }
@@ -41,8 +35,8 @@ fn part1(input: Input) -> i64 {
 }
 
 fn part2(input: Input) -> i64 {
-    let compressed_x = compress_coordinates(input.iter().map(|(x, _)| *x).collect());
-    let compressed_y = compress_coordinates(input.iter().map(|(_, y)| *y).collect());
+    let compressed_x = compress_coordinates(input.iter().map(|p| p.x).collect());
+    let compressed_y = compress_coordinates(input.iter().map(|p| p.y).collect());
 
     let mut space = vec![vec![None; compressed_x.len()]; compressed_y.len()];
     fill_lines(&input, &compressed_x, &compressed_y, &mut space);
@@ -51,10 +45,10 @@ fn part2(input: Input) -> i64 {
     let mut max_size = 0;
     for i in 0..input.len() {
         for j in i + 1..input.len() {
-            let (x1, y1) = input[i];
-            let (x2, y2) = input[j];
+            let Point2D { x: x1, y: y1 } = input[i];
+            let Point2D { x: x2, y: y2 } = input[j];
 
-            let size = ((x2 - x1).abs() + 1) * ((y2 - y1).abs() + 1);
+            let size = area(input[i], input[j]);
             if size > max_size {
                 let min_x = compressed_x[&x1.min(x2)];
                 let min_y = compressed_y[&y1.min(y2)];
@@ -71,6 +65,12 @@ fn part2(input: Input) -> i64 {
     max_size
 }
 
+/// The area of the rectangle with the given opposite corner tiles.
+fn area(a: Point2D, b: Point2D) -> i64 {
+    let offset = b - a;
+    (offset.x.abs() + 1) * (offset.y.abs() + 1)
+}
+
 fn compress_coordinates(mut coords: Vec<i64>) -> HashMap<i64, usize> {
     coords.sort_unstable();
     coords.dedup();
@@ -90,8 +90,8 @@ fn fill_lines(
     space: &mut [Vec<Option<usize>>],
 ) {
     for i in 0..input.len() {
-        let (x1, y1) = input[i];
-        let (x2, y2) = input[(i + 1) % input.len()];
+        let Point2D { x: x1, y: y1 } = input[i];
+        let Point2D { x: x2, y: y2 } = input[(i + 1) % input.len()];
 
         let cx1 = compressed_x[&x1];
         let cy1 = compressed_y[&y1];
@@ -148,10 +148,7 @@ fn valid_rect(
     true
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 09, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -160,7 +157,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 4781546175);
         assert_eq!(part2(input), 1573359081);
     }
@@ -176,7 +173,8 @@ mod tests {
 2,5
 2,3
 7,3",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 50);
         assert_eq!(part2(input), 24);
     }
@@ -1,7 +1,9 @@
-use advent::prelude::*;
+use advent_search::bfs;
 use z3::Optimize;
 use z3::ast::Int;
 
+use crate::parse::{ParseError, Source};
+
 type Input = Vec<MachineInfo>;
 
 #[derive(Debug, Clone)]
@@ -11,14 +13,21 @@ struct MachineInfo {
     joltages: Vec<usize>,
 }
 
-fn parse_input(input: &str) -> Input {
-    input.lines().map(parse_machine).collect()
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    input
+        .lines()
+        .map(|line| parse_machine(source, line))
+        .collect()
 }
 
-fn parse_machine(line: &str) -> MachineInfo {
+fn parse_machine(source: Source<'_>, line: &str) -> Result<MachineInfo, ParseError> {
     let words: Vec<_> = line.split_whitespace().collect();
+    let [first, .., last] = words[..] else {
+        return Err(source.error(line, "expected lights, buttons and joltages"));
+    };
 
-    let lights = words[0]
+    let lights: Vec<_> = first
         .trim_matches(|c| c == '[' || c == ']')
         .chars()
         .map(|c| c == '#')
@@ -29,72 +38,52 @@ fn parse_machine(line: &str) -> MachineInfo {
         .map(|w| {
             w.trim_matches(|c| c == '(' || c == ')')
                 .split(',')
-                .map(|num| num.parse::<usize>().unwrap())
-                .collect::<Vec<usize>>()
+                .map(|num| match source.parse::<usize>(num)? {
+                    light if light < lights.len() => Ok(light),
+                    _ => Err(source.error(num, "no such light")),
+                })
+                .collect::<Result<Vec<usize>, _>>()
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    let joltages = words[words.len() - 1]
+    let joltages = last
         .trim_matches(|c| c == '{' || c == '}')
         .split(',')
-        .map(|num| num.parse::<usize>().unwrap())
-        .collect();
+        .map(|num| source.parse::<usize>(num))
+        .collect::<Result<_, _>>()?;
 
-    MachineInfo {
+    Ok(MachineInfo {
         lights,
         buttons,
         joltages,
-    }
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 10));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    })
 }
 
 fn part1(input: Input) -> usize {
-    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-    struct QueueState {
-        light_states: Vec<bool>,
-        steps: usize,
-    }
-
     input
         .iter()
         .map(|machine| {
-            let mut queue = VecDeque::new();
-            queue.push_back(QueueState {
-                light_states: vec![false; machine.lights.len()],
-                steps: 0,
-            });
-
-            let mut seen_states = HashSet::new();
-
-            while let Some(state) = queue.pop_front() {
-                if seen_states.contains(&state.light_states) {
-                    continue;
-                }
-                if machine.lights == state.light_states {
-                    return state.steps;
-                }
-
-                seen_states.insert(state.light_states.clone());
-
-                for button in &machine.buttons {
-                    let mut new_lights = state.light_states.clone();
-                    for &idx in button {
-                        new_lights[idx] = !new_lights[idx];
-                    }
-                    queue.push_back(QueueState {
-                        light_states: new_lights,
-                        steps: state.steps + 1,
-                    });
-                }
-            }
-
-            panic!("no solution found for machine {:?}", machine);
+            let search = bfs(
+                vec![false; machine.lights.len()],
+                |lights: &Vec<bool>| {
+                    machine
+                        .buttons
+                        .iter()
+                        .map(|button| {
+                            let mut new_lights = lights.clone();
+                            for &idx in button {
+                                new_lights[idx] = !new_lights[idx];
+                            }
+                            new_lights
+                        })
+                        .collect::<Vec<_>>()
+                },
+                |lights| *lights == machine.lights,
+            );
+
+            search
+                .goal_cost()
+                .unwrap_or_else(|| panic!("no solution found for machine {:?}", machine))
         })
         .sum()
 }
@@ -137,10 +126,7 @@ fn part2(input: Input) -> i64 {
         .sum()
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 10, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -149,7 +135,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 473);
         assert_eq!(part2(input), 18681);
     }
@@ -160,7 +146,8 @@ mod tests {
             "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
 [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
 [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input.clone()), 7);
         assert_eq!(part2(input), 33);
     }
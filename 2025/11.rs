@@ -1,3 +1,4 @@
+use crate::parse::{ParseError, Source};
 use advent::prelude::*;
 
 #[derive(Debug, Clone)]
@@ -5,13 +6,14 @@ struct Input {
     reverse_deps: HashMap<String, Vec<String>>,
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let mut reverse_deps: HashMap<String, Vec<String>> = HashMap::new();
 
     for line in input.lines() {
-        let mut parts = line.split(':');
-        let name = parts.next().unwrap().trim();
-        let rest = parts.next().unwrap().trim();
+        let mut parts = source.fields(line, line.split(':'));
+        let name = parts.field()?.trim();
+        let rest = parts.field()?.trim();
         let dependencies: Vec<String> = rest.split_whitespace().map(|s| s.to_owned()).collect();
 
         for dep in &dependencies {
@@ -22,14 +24,7 @@ fn parse_input(input: &str) -> Input {
         }
     }
 
-    Input { reverse_deps }
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 11));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(Input { reverse_deps })
 }
 
 fn part1(input: Input) -> i64 {
@@ -111,10 +106,7 @@ fn get_num_paths_2(input: &Input, paths: &mut HashMap<VisitedKey, i64>, node: Vi
     count
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
-}
+solution!(2025 / 11, part1, part2);
 
 #[cfg(test)]
 mod tests {
@@ -123,7 +115,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 764);
         assert_eq!(part2(input), 462444153119850);
     }
@@ -142,7 +134,8 @@ ggg: out
 hhh: ccc fff iii
 iii: out
 ",
-        );
+        )
+        .unwrap();
         assert_eq!(part1(input), 5);
 
         let input = parse_input(
@@ -160,7 +153,8 @@ fff: ggg hhh
 ggg: out
 hhh: out
 ",
-        );
+        )
+        .unwrap();
         assert_eq!(part2(input), 2);
     }
 }
@@ -1,4 +1,4 @@
-use advent::prelude::*;
+use crate::parse::{ParseError, Source};
 
 #[derive(Debug, Clone)]
 struct Input {
@@ -34,8 +34,10 @@ struct Query {
     shape_counts: Vec<i64>,
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let lines = input.lines().collect::<Vec<&str>>();
+    let line = |index: usize| lines.get(index).ok_or_else(|| source.end_of_input());
     let mut index = 0;
     let mut shapes = vec![];
 
@@ -43,17 +45,18 @@ fn parse_input(input: &str) -> Input {
         let mut shape = vec![];
         index += 1; // skip shape index line
         for _ in 0..3 {
-            let line = lines[index];
+            let line = line(index)?.trim();
             index += 1;
             let row = line
-                .trim()
-                .chars()
-                .map(|c| match c {
-                    '#' => true,
-                    '.' => false,
-                    _ => panic!("invalid character in shape"),
+                .char_indices()
+                .map(|(i, c)| match c {
+                    '#' => Ok(true),
+                    '.' => Ok(false),
+                    _ => {
+                        Err(source.error(&line[i..i + c.len_utf8()], "invalid character in shape"))
+                    }
                 })
-                .collect::<Vec<bool>>();
+                .collect::<Result<Vec<bool>, _>>()?;
             shape.push(row);
         }
         shapes.push(Shape::new(shape));
@@ -64,18 +67,18 @@ fn parse_input(input: &str) -> Input {
     while index < lines.len() {
         let line = lines[index];
         index += 1;
-        let mut parts = line.split(':');
-        let size_part = parts.next().unwrap().trim();
-        let counts_part = parts.next().unwrap().trim();
+        let mut parts = source.fields(line, line.split(':'));
+        let size_part = parts.field()?.trim();
+        let counts_part = parts.field()?.trim();
 
-        let mut size_parts = size_part.split('x');
-        let width: i64 = size_parts.next().unwrap().trim().parse().unwrap();
-        let height: i64 = size_parts.next().unwrap().trim().parse().unwrap();
+        let mut size_parts = source.fields(size_part, size_part.split('x'));
+        let width: i64 = source.parse(size_parts.field()?.trim())?;
+        let height: i64 = source.parse(size_parts.field()?.trim())?;
 
         let shape_counts = counts_part
             .split_whitespace()
-            .map(|s| s.parse::<i64>().unwrap())
-            .collect::<Vec<i64>>();
+            .map(|s| source.parse::<i64>(s))
+            .collect::<Result<Vec<i64>, _>>()?;
 
         queries.push(Query {
             width,
@@ -84,14 +87,7 @@ fn parse_input(input: &str) -> Input {
         });
     }
 
-    Input { shapes, queries }
-}
-
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!(2025 / 12));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+    Ok(Input { shapes, queries })
 }
 
 fn part1(input: Input) -> i64 {
@@ -112,10 +108,7 @@ fn part1(input: Input) -> i64 {
         .sum()
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).build();
-    solution.cli()
-}
+solution!(2025 / 12, part1);
 
 #[cfg(test)]
 mod tests {
@@ -124,7 +117,7 @@ mod tests {
     #[ignore]
     #[test]
     fn default() {
-        let input = default_input();
+        let input = default_input().unwrap();
         assert_eq!(part1(input.clone()), 427);
     }
 }
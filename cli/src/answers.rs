@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// The answers ledger stored in `answers.toml` in the workspace root.
+///
+/// Answers are keyed by year, day and part, e.g. `[2024.06.part1]`.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Answers {
+    years: BTreeMap<String, BTreeMap<String, BTreeMap<String, Part>>>,
+}
+
+/// Everything we know about the answer to a single puzzle part.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct Part {
+    /// The answer accepted by the site.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+
+    /// Answers that were submitted and rejected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejected: Vec<Rejected>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Rejected {
+    pub answer: String,
+    pub reason: Reason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Reason {
+    TooHigh,
+    TooLow,
+    Incorrect,
+}
+
+/// Returns the ledger path, profiles other than the default one each have
+/// their own `answers.{profile}.toml` since their inputs differ.
+pub fn path(workspace_dir: &Path, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(profile) => workspace_dir.join(format!("answers.{profile}.toml")),
+        None => workspace_dir.join("answers.toml"),
+    }
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: u32) -> Option<&Part> {
+        self.years
+            .get(&format!("{year:04}"))?
+            .get(&format!("{day:02}"))?
+            .get(&format!("part{part}"))
+    }
+
+    pub fn entry(&mut self, year: u32, day: u32, part: u32) -> &mut Part {
+        self.years
+            .entry(format!("{year:04}"))
+            .or_default()
+            .entry(format!("{day:02}"))
+            .or_default()
+            .entry(format!("part{part}"))
+            .or_default()
+    }
+}
+
+impl Part {
+    /// Returns why the given answer is known to be wrong, if it is.
+    ///
+    /// Besides exact matches this also rules out numeric answers that are
+    /// beyond a previous "too high" or "too low" verdict.
+    pub fn known_wrong(&self, answer: &str) -> Option<Reason> {
+        if let Some(correct) = &self.answer {
+            return (correct != answer).then_some(Reason::Incorrect);
+        }
+        let value = answer.parse::<i128>().ok();
+        self.rejected.iter().find_map(|r| {
+            if r.answer == answer {
+                return Some(r.reason);
+            }
+            let bound = r.answer.parse::<i128>().ok()?;
+            match r.reason {
+                Reason::TooHigh if value? >= bound => Some(Reason::TooHigh),
+                Reason::TooLow if value? <= bound => Some(Reason::TooLow),
+                _ => None,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut answers = Answers::default();
+        answers.entry(2024, 6, 1).answer = Some("5208".into());
+        answers.entry(2024, 6, 2).rejected.push(Rejected {
+            answer: "2000".into(),
+            reason: Reason::TooHigh,
+        });
+        let text = toml::to_string(&answers).unwrap();
+        assert!(text.contains("[2024.06.part1]"));
+        assert!(text.contains("too-high"));
+
+        let answers: Answers = toml::from_str(&text).unwrap();
+        assert_eq!(
+            answers.get(2024, 6, 1).unwrap().answer.as_deref(),
+            Some("5208")
+        );
+        assert_eq!(answers.get(2024, 6, 2).unwrap().rejected.len(), 1);
+        assert!(answers.get(2024, 7, 1).is_none());
+    }
+
+    #[test]
+    fn known_wrong() {
+        let mut part = Part::default();
+        part.rejected.push(Rejected {
+            answer: "2000".into(),
+            reason: Reason::TooHigh,
+        });
+        part.rejected.push(Rejected {
+            answer: "1000".into(),
+            reason: Reason::TooLow,
+        });
+        part.rejected.push(Rejected {
+            answer: "abc".into(),
+            reason: Reason::Incorrect,
+        });
+        assert_eq!(part.known_wrong("2000"), Some(Reason::TooHigh));
+        assert_eq!(part.known_wrong("2500"), Some(Reason::TooHigh));
+        assert_eq!(part.known_wrong("999"), Some(Reason::TooLow));
+        assert_eq!(part.known_wrong("abc"), Some(Reason::Incorrect));
+        assert_eq!(part.known_wrong("1972"), None);
+        assert_eq!(part.known_wrong("xyz"), None);
+
+        part.answer = Some("1972".into());
+        assert_eq!(part.known_wrong("1972"), None);
+        assert_eq!(part.known_wrong("1973"), Some(Reason::Incorrect));
+    }
+}
@@ -0,0 +1,49 @@
+use crate::html::{self, Node};
+
+/// The error for a session cookie the site does not accept.
+pub const LOGGED_OUT: &str = "not logged in, the session cookie is invalid or has expired";
+
+/// Returns whether a response is the site asking us to log in, which is what
+/// an invalid or expired session cookie gets instead of an input or verdict.
+pub fn is_logged_out(body: &str) -> bool {
+    let body = body.to_lowercase();
+    body.contains("please log in") || body.contains("please identify yourself")
+}
+
+/// Returns the name of the logged in user shown in the page header.
+pub fn user(page: &str) -> Option<String> {
+    let nodes = html::parse(page);
+    let header = html::find_all(&nodes, &|e| e.name == "div" && e.has_class("user"));
+    let name: String = header
+        .first()?
+        .children
+        .iter()
+        .filter_map(|n| match n {
+            Node::Text(text) => Some(text.as_str()),
+            Node::Element(_) => None,
+        })
+        .collect();
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logged_in() {
+        let page = include_str!("../tests/fixtures/settings.html");
+        assert_eq!(user(page).as_deref(), Some("octocat"));
+        assert!(!is_logged_out(page));
+    }
+
+    #[test]
+    fn logged_out() {
+        let input = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        assert!(is_logged_out(input));
+        let page = include_str!("../tests/fixtures/login.html");
+        assert!(is_logged_out(page));
+        assert_eq!(user(page), None);
+    }
+}
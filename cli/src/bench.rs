@@ -0,0 +1,411 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::{runner, target_dir};
+
+/// How benchmark results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => bail!("expected one of: text, json, csv"),
+        }
+    }
+}
+
+/// Summary statistics over a number of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "mean_ns", with = "nanos")]
+    pub mean: Duration,
+    #[serde(rename = "median_ns", with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "stddev_ns", with = "nanos")]
+    pub stddev: Duration,
+}
+
+/// (De)serializes durations as a whole number of nanoseconds.
+mod nanos {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(d.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        u64::deserialize(d).map(Duration::from_nanos)
+    }
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        let variance = sorted
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Some(Self {
+            min: sorted[0],
+            mean,
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// The benchmark results of a single day.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DayBench {
+    pub year: u32,
+    pub day: u32,
+    pub samples: usize,
+    /// Statistics keyed by stage: `parse`, `part1` or `part2`.
+    pub timings: BTreeMap<String, Stats>,
+}
+
+/// Benchmarks a day by running its binary in bench mode `samples` times.
+///
+/// Every run reports one timing per stage; the statistics are computed over
+/// those runs.
+pub fn measure(year: u32, day: u32, samples: usize, args: &[&str]) -> Result<DayBench> {
+    let args: Vec<_> = ["--bench"]
+        .into_iter()
+        .chain(args.iter().copied())
+        .collect();
+
+    let mut timings = BTreeMap::<String, Vec<Duration>>::new();
+    for _ in 0..samples {
+        let output = runner::parse_output(&runner::run(year, day, &args)?);
+        if let Some(time) = output.parse {
+            timings.entry("parse".into()).or_default().push(time);
+        }
+        for (part, runner::Part { time, .. }) in output.parts {
+            if let Some(time) = time {
+                timings.entry(format!("part{part}")).or_default().push(time);
+            }
+        }
+    }
+
+    Ok(DayBench {
+        year,
+        day,
+        samples,
+        timings: timings
+            .into_iter()
+            .filter_map(|(stage, samples)| Some((stage, Stats::from_samples(&samples)?)))
+            .collect(),
+    })
+}
+
+/// Renders benchmark results in the given format.
+pub fn render(results: &[DayBench], format: Format) -> Result<String> {
+    let mut out = String::new();
+    match format {
+        Format::Text => {
+            writeln!(
+                out,
+                "{:<9}{:<7}{:>12}{:>12}{:>12}{:>12}",
+                "Day", "Stage", "Min", "Mean", "Median", "Stddev"
+            )?;
+            for r in results {
+                for (stage, s) in &r.timings {
+                    writeln!(
+                        out,
+                        "{:04}/{:02}  {stage:<7}{:>12}{:>12}{:>12}{:>12}",
+                        r.year,
+                        r.day,
+                        format!("{:.2?}", s.min),
+                        format!("{:.2?}", s.mean),
+                        format!("{:.2?}", s.median),
+                        format!("{:.2?}", s.stddev),
+                    )?;
+                }
+            }
+        }
+        Format::Json => {
+            out = serde_json::to_string_pretty(results)?;
+            out.push('\n');
+        }
+        Format::Csv => {
+            writeln!(
+                out,
+                "year,day,stage,samples,min_ns,mean_ns,median_ns,stddev_ns"
+            )?;
+            for r in results {
+                for (stage, s) in &r.timings {
+                    writeln!(
+                        out,
+                        "{},{},{stage},{},{},{},{},{}",
+                        r.year,
+                        r.day,
+                        r.samples,
+                        s.min.as_nanos(),
+                        s.mean.as_nanos(),
+                        s.median.as_nanos(),
+                        s.stddev.as_nanos(),
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn baseline_path(name: &str) -> PathBuf {
+    target_dir().join("baselines").join(format!("{name}.json"))
+}
+
+pub fn load_baseline(name: &str) -> Result<Vec<DayBench>> {
+    let path = baseline_path(name);
+    let text = fs::read_to_string(&path)
+        .with_context(|| format!("no baseline named `{name}` at `{}`", path.display()))?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Stores the results under the given baseline name.
+///
+/// Days that were not benchmarked this time keep their previous results.
+pub fn save_baseline(name: &str, results: &[DayBench]) -> Result<PathBuf> {
+    let path = baseline_path(name);
+    let mut baseline = if path.exists() {
+        load_baseline(name)?
+    } else {
+        Vec::new()
+    };
+    baseline.retain(|b| !results.iter().any(|r| (r.year, r.day) == (b.year, b.day)));
+    baseline.extend_from_slice(results);
+    baseline.sort_by_key(|b| (b.year, b.day));
+
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, serde_json::to_string_pretty(&baseline)?)?;
+    Ok(path)
+}
+
+/// How the timing of one stage changed relative to a baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub year: u32,
+    pub day: u32,
+    pub stage: String,
+    pub old: Duration,
+    pub new: Duration,
+    /// The relative change of the mean, in percent.
+    pub delta: f64,
+    /// Whether the change is statistically significant.
+    pub significant: bool,
+    /// Whether this is a significant slowdown beyond the threshold.
+    pub regressed: bool,
+}
+
+/// Compares the results with a baseline, stage by stage.
+///
+/// `threshold` is the slowdown in percent above which a significant change
+/// counts as a regression.
+pub fn compare(baseline: &[DayBench], results: &[DayBench], threshold: f64) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for r in results {
+        let Some(b) = baseline.iter().find(|b| (b.year, b.day) == (r.year, r.day)) else {
+            continue;
+        };
+        for (stage, new) in &r.timings {
+            let Some(old) = b.timings.get(stage) else {
+                continue;
+            };
+            let delta = (new.mean.as_secs_f64() / old.mean.as_secs_f64() - 1.0) * 100.0;
+            let significant = welch_significant((old, b.samples), (new, r.samples));
+            changes.push(Change {
+                year: r.year,
+                day: r.day,
+                stage: stage.clone(),
+                old: old.mean,
+                new: new.mean,
+                delta,
+                significant,
+                regressed: significant && delta > threshold,
+            });
+        }
+    }
+    changes
+}
+
+/// Runs a two-sided Welch's t-test at the 95% confidence level.
+fn welch_significant((a, n_a): (&Stats, usize), (b, n_b): (&Stats, usize)) -> bool {
+    if n_a < 2 || n_b < 2 {
+        // Without any spread information only report changes beyond the noise
+        // typically seen between runs.
+        return a.mean.abs_diff(b.mean) > a.mean.max(b.mean) / 10;
+    }
+    let var = |s: &Stats, n: usize| s.stddev.as_secs_f64().powi(2) / n as f64;
+    let (v_a, v_b) = (var(a, n_a), var(b, n_b));
+    let diff = (a.mean.as_secs_f64() - b.mean.as_secs_f64()).abs();
+    if v_a + v_b == 0.0 {
+        return diff > 0.0;
+    }
+    let t = diff / (v_a + v_b).sqrt();
+    let df =
+        (v_a + v_b).powi(2) / (v_a.powi(2) / (n_a - 1) as f64 + v_b.powi(2) / (n_b - 1) as f64);
+    t > t_critical(df)
+}
+
+/// Two-sided 95% critical values of Student's t-distribution.
+fn t_critical(df: f64) -> f64 {
+    const TABLE: [(f64, f64); 13] = [
+        (1.0, 12.706),
+        (2.0, 4.303),
+        (3.0, 3.182),
+        (4.0, 2.776),
+        (5.0, 2.571),
+        (6.0, 2.447),
+        (7.0, 2.365),
+        (8.0, 2.306),
+        (9.0, 2.262),
+        (10.0, 2.228),
+        (15.0, 2.131),
+        (20.0, 2.086),
+        (30.0, 2.042),
+    ];
+    if df > 30.0 {
+        return 1.96;
+    }
+    TABLE
+        .iter()
+        .rev()
+        .find(|(d, _)| df >= *d)
+        .map_or(TABLE[0].1, |(_, t)| *t)
+}
+
+pub fn render_comparison(changes: &[Change]) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:<9}{:<7}{:>12}{:>12}{:>10}",
+        "Day", "Stage", "Baseline", "New", "Change"
+    )
+    .unwrap();
+    for c in changes {
+        let verdict = match (c.regressed, c.significant, c.delta < 0.0) {
+            (true, _, _) => "REGRESSED",
+            (_, true, true) => "faster",
+            (_, true, false) => "slower",
+            (_, false, _) => "no change",
+        };
+        writeln!(
+            out,
+            "{:04}/{:02}  {:<7}{:>12}{:>12}{:>+9.1}%  {verdict}",
+            c.year,
+            c.day,
+            c.stage,
+            format!("{:.2?}", c.old),
+            format!("{:.2?}", c.new),
+            c.delta,
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(3), ms(2)]).unwrap();
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1118);
+
+        let stats = Stats::from_samples(&[ms(5)]).unwrap();
+        assert_eq!(stats.median, ms(5));
+        assert_eq!(stats.stddev, Duration::ZERO);
+
+        assert!(Stats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn csv() {
+        let results = [DayBench {
+            year: 2024,
+            day: 6,
+            samples: 1,
+            timings: [("part1".into(), Stats::from_samples(&[ms(2)]).unwrap())].into(),
+        }];
+        assert_eq!(
+            render(&results, Format::Csv).unwrap(),
+            "year,day,stage,samples,min_ns,mean_ns,median_ns,stddev_ns\n\
+             2024,6,part1,1,2000000,2000000,2000000,0\n"
+        );
+        let json = render(&results, Format::Json).unwrap();
+        assert!(json.contains("\"median_ns\": 2000000"));
+        let parsed: Vec<DayBench> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0].timings["part1"], results[0].timings["part1"]);
+    }
+
+    fn day(samples: &[u64]) -> DayBench {
+        let samples: Vec<_> = samples.iter().map(|&s| ms(s)).collect();
+        DayBench {
+            year: 2024,
+            day: 6,
+            samples: samples.len(),
+            timings: [("part2".into(), Stats::from_samples(&samples).unwrap())].into(),
+        }
+    }
+
+    #[test]
+    fn comparison() {
+        let baseline = [day(&[100, 102, 98, 101, 99])];
+
+        let faster = compare(&baseline, &[day(&[50, 51, 49, 50, 50])], 5.0);
+        assert_eq!(faster.len(), 1);
+        assert!(faster[0].significant && !faster[0].regressed);
+        assert!((faster[0].delta + 50.0).abs() < 0.1);
+
+        let noisy = compare(&baseline, &[day(&[90, 110, 100, 105, 96])], 5.0);
+        assert!(!noisy[0].significant);
+
+        let slower = compare(&baseline, &[day(&[120, 121, 119, 120, 120])], 5.0);
+        assert!(slower[0].regressed);
+        let slower = compare(&baseline, &[day(&[120, 121, 119, 120, 120])], 25.0);
+        assert!(slower[0].significant && !slower[0].regressed);
+    }
+}
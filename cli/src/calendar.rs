@@ -0,0 +1,134 @@
+//! The event calendar: which days each year has and when they unlock.
+
+use anyhow::{Result, bail};
+use time::macros::{format_description, offset, time};
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+/// The first year with puzzles.
+pub const FIRST_YEAR: u32 = 2015;
+
+/// The rules of one year's event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// The number of puzzles, one a day from December 1st.
+    pub days: u32,
+    /// The time of day puzzles unlock at, in `time_zone`.
+    pub unlocks_at: Time,
+    pub time_zone: UtcOffset,
+}
+
+/// Returns the rules of a year's event.
+pub fn rules(year: u32) -> Result<Rules> {
+    // Puzzles unlock at midnight US Eastern Standard Time, December has no
+    // daylight saving time.
+    let (unlocks_at, time_zone) = (time!(0:00), offset!(-5));
+    match year {
+        ..FIRST_YEAR => bail!("there are no puzzles before {FIRST_YEAR}"),
+        FIRST_YEAR..=2024 => Ok(Rules {
+            days: 25,
+            unlocks_at,
+            time_zone,
+        }),
+        _ => Ok(Rules {
+            days: 12,
+            unlocks_at,
+            time_zone,
+        }),
+    }
+}
+
+/// Checks that the year has a puzzle on the day.
+pub fn validate(year: u32, day: u32) -> Result<()> {
+    let days = rules(year)?.days;
+    if !(1..=days).contains(&day) {
+        bail!("{year} has puzzles on days 1 to {days}, not on day {day}");
+    }
+    Ok(())
+}
+
+/// Returns the number of puzzle parts of the day, the last day only has one.
+pub fn parts(year: u32, day: u32) -> Result<u32> {
+    validate(year, day)?;
+    Ok(if day == rules(year)?.days { 1 } else { 2 })
+}
+
+/// Returns when the puzzle unlocks.
+pub fn unlock(year: u32, day: u32) -> Result<OffsetDateTime> {
+    validate(year, day)?;
+    let rules = rules(year)?;
+    let date = Date::from_calendar_date(year as i32, Month::December, day as u8)?;
+    Ok(PrimitiveDateTime::new(date, rules.unlocks_at).assume_offset(rules.time_zone))
+}
+
+/// Formats an unlock time, e.g. `2024-12-06 00:00 UTC-05:00`.
+pub fn format(time: OffsetDateTime) -> String {
+    let format = format_description!(
+        "[year]-[month]-[day] [hour]:[minute] UTC[offset_hour sign:mandatory]:[offset_minute]"
+    );
+    time.format(format).unwrap()
+}
+
+/// Formats the time left until an unlock, e.g. `2d 03h 04m 05s`.
+pub fn countdown(remaining: Duration) -> String {
+    // Round up, so that the countdown reaches zero when the puzzle unlocks.
+    let secs = (remaining + Duration::seconds(1) - Duration::NANOSECOND).whole_seconds();
+    let (days, hours, minutes, secs) = (
+        secs / 86400,
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{secs}s"),
+        (0, 0, _) => format!("{minutes}m {secs:02}s"),
+        (0, _, _) => format!("{hours}h {minutes:02}m {secs:02}s"),
+        _ => format!("{days}d {hours:02}h {minutes:02}m {secs:02}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn days() {
+        assert_eq!(rules(2015).unwrap().days, 25);
+        assert_eq!(rules(2024).unwrap().days, 25);
+        assert_eq!(rules(2025).unwrap().days, 12);
+        assert!(rules(2014).is_err());
+
+        assert!(validate(2024, 25).is_ok());
+        assert_eq!(
+            validate(2025, 13).unwrap_err().to_string(),
+            "2025 has puzzles on days 1 to 12, not on day 13"
+        );
+        assert!(validate(2024, 0).is_err());
+        assert!(validate(2024, 30).is_err());
+
+        assert_eq!(parts(2024, 24).unwrap(), 2);
+        assert_eq!(parts(2024, 25).unwrap(), 1);
+        assert_eq!(parts(2025, 12).unwrap(), 1);
+    }
+
+    #[test]
+    fn unlocks() {
+        let unlock = unlock(2024, 6).unwrap();
+        assert_eq!(unlock, datetime!(2024-12-06 05:00 UTC));
+        assert_eq!(format(unlock), "2024-12-06 00:00 UTC-05:00");
+        assert!(datetime!(2024-12-06 04:59:59 UTC) < unlock);
+    }
+
+    #[test]
+    fn countdowns() {
+        assert_eq!(countdown(Duration::milliseconds(1)), "1s");
+        assert_eq!(countdown(Duration::seconds(1)), "1s");
+        assert_eq!(countdown(Duration::milliseconds(59_500)), "1m 00s");
+        assert_eq!(countdown(Duration::seconds(3 * 3600 + 65)), "3h 01m 05s");
+        assert_eq!(
+            countdown(Duration::seconds(2 * 86400 + 3 * 3600 + 4 * 60 + 5)),
+            "2d 03h 04m 05s"
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! The HTTP client every request to the site goes through.
+//!
+//! Requests are spaced at least `request-interval` apart, even across
+//! separate runs of the CLI, retried with exponential backoff on server
+//! errors, and GET responses carrying an `ETag` or `Last-Modified` header are
+//! cached on disk and revalidated with conditional requests.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::{auth, config, target_dir};
+
+const DEFAULT_USER_AGENT: &str = "github.com/m4tx/advent-of-code";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+const RETRIES: u32 = 3;
+const BACKOFF: Duration = Duration::from_millis(500);
+
+/// Downloads a page with the session cookie of the selected profile.
+pub fn get(url: &str) -> Result<String> {
+    get_as(url, &config::get().session()?)
+}
+
+/// Downloads a page with the given session cookie.
+pub fn get_as(url: &str, session: &str) -> Result<String> {
+    let path = cache_path(url, session);
+    let cached: Option<Cached> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok());
+
+    let mut headers = Vec::new();
+    if let Some(cached) = &cached {
+        if let Some(etag) = &cached.etag {
+            headers.push(format!("If-None-Match: {etag}"));
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            headers.push(format!("If-Modified-Since: {last_modified}"));
+        }
+    }
+
+    let response = send(url, None, session, &headers)?;
+    if response.code == 304
+        && let Some(cached) = cached
+    {
+        return Ok(cached.body);
+    }
+    let body = check(url, response.code, response.body)?;
+
+    if response.etag.is_some() || response.last_modified.is_some() {
+        let cached = Cached {
+            etag: response.etag,
+            last_modified: response.last_modified,
+            body,
+        };
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, serde_json::to_string(&cached)?)?;
+        return Ok(cached.body);
+    }
+    Ok(body)
+}
+
+/// Posts a form with the session cookie of the selected profile.
+pub fn post(url: &str, form: &str) -> Result<String> {
+    let response = send(url, Some(form), &config::get().session()?, &[])?;
+    check(url, response.code, response.body)
+}
+
+/// The user agent sent with every request, overridable with
+/// `ADVENT_USER_AGENT` or `user-agent` in the config.
+fn user_agent() -> String {
+    env::var("ADVENT_USER_AGENT")
+        .ok()
+        .or_else(|| config::get().user_agent.clone())
+        .unwrap_or_else(|| DEFAULT_USER_AGENT.into())
+}
+
+/// The minimum time between requests, overridable with
+/// `ADVENT_REQUEST_INTERVAL` or `request-interval` in the config, in seconds.
+fn interval() -> Result<Duration> {
+    let secs = match env::var("ADVENT_REQUEST_INTERVAL") {
+        Ok(secs) => Some(
+            secs.parse()
+                .with_context(|| format!("`ADVENT_REQUEST_INTERVAL` is not a number: `{secs}`"))?,
+        ),
+        Err(_) => config::get().request_interval,
+    };
+    Ok(secs.map_or(DEFAULT_INTERVAL, Duration::from_secs_f64))
+}
+
+fn cache_dir() -> PathBuf {
+    target_dir().join("http-cache")
+}
+
+/// Responses are cached per session since inputs differ between accounts.
+fn cache_path(url: &str, session: &str) -> PathBuf {
+    cache_dir().join(format!("{:016x}.json", fnv1a(&[session, "\0", url])))
+}
+
+fn fnv1a(parts: &[&str]) -> u64 {
+    let mut hash = 0xcbf29ce484222325_u64;
+    for b in parts.iter().flat_map(|p| p.bytes()) {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Cached {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+#[derive(Debug, Default)]
+struct Response {
+    code: u32,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+/// The error for a puzzle or input that has not been released yet.
+#[derive(Debug)]
+pub struct NotUnlocked(String);
+
+impl std::fmt::Display for NotUnlocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is not unlocked yet", self.0)
+    }
+}
+
+impl std::error::Error for NotUnlocked {}
+
+/// Turns error responses into errors.
+///
+/// Only error responses and the short ones of the input and answer endpoints
+/// are checked for a request to log in, as puzzle text may contain the words.
+fn check(url: &str, code: u32, body: String) -> Result<String> {
+    let login_endpoint = url.ends_with("/input") || url.ends_with("/answer");
+    if (code != 200 || login_endpoint) && auth::is_logged_out(&body) {
+        bail!(auth::LOGGED_OUT);
+    }
+    match code {
+        404 if url.contains("/day/") => Err(NotUnlocked(url.into()).into()),
+        400.. => bail!("`{url}` responded with HTTP {code}"),
+        _ => Ok(body),
+    }
+}
+
+/// Sends a request, retrying server errors with exponential backoff.
+fn send(url: &str, form: Option<&str>, session: &str, headers: &[String]) -> Result<Response> {
+    let mut backoff = BACKOFF;
+    for attempt in 0.. {
+        throttle()?;
+        let response = perform(url, form, session, headers)?;
+        if response.code < 500 || attempt == RETRIES {
+            return Ok(response);
+        }
+        crate::warning(
+            "Retrying",
+            format!(
+                "`{url}` responded with HTTP {} in {backoff:?}",
+                response.code
+            ),
+        );
+        thread::sleep(backoff);
+        backoff *= 2;
+    }
+    unreachable!()
+}
+
+/// Waits until the minimum interval since the last request has passed, the
+/// time of which is kept on disk so that it also applies across runs.
+fn throttle() -> Result<()> {
+    let path = cache_dir().join("last-request");
+    let interval = interval()?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let last = fs::read_to_string(&path)
+        .ok()
+        .and_then(|millis| millis.trim().parse().ok())
+        .map(Duration::from_millis);
+    if let Some(wait) = last.and_then(|last| (last + interval).checked_sub(now)) {
+        thread::sleep(wait.min(interval));
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    fs::create_dir_all(cache_dir())?;
+    fs::write(&path, now.as_millis().to_string())?;
+    Ok(())
+}
+
+fn perform(url: &str, form: Option<&str>, session: &str, headers: &[String]) -> Result<Response> {
+    let mut response = Response::default();
+    let mut buf = Vec::new();
+    let mut easy = curl::easy::Easy::new();
+    easy.follow_location(true)?;
+    easy.useragent(&user_agent())?;
+    easy.cookie(&format!("session={session}"))?;
+    easy.url(url)?;
+    let mut list = curl::easy::List::new();
+    for header in headers {
+        list.append(header)?;
+    }
+    easy.http_headers(list)?;
+    if let Some(form) = form {
+        easy.post(true)?;
+        easy.post_fields_copy(form.as_bytes())?;
+    }
+    {
+        let mut transfer = easy.transfer();
+        transfer.header_function(|line| {
+            let line = String::from_utf8_lossy(line);
+            if let Some((name, value)) = line.split_once(':') {
+                let value = Some(value.trim().to_owned());
+                match name.trim().to_ascii_lowercase().as_str() {
+                    "etag" => response.etag = value,
+                    "last-modified" => response.last_modified = value,
+                    _ => {}
+                }
+            }
+            true
+        })?;
+        transfer.write_function(|data| {
+            buf.extend_from_slice(data);
+            Ok(data.len())
+        })?;
+        transfer.perform()?;
+    }
+    response.code = easy.response_code()?;
+    response.body = String::from_utf8(buf)?;
+    log(form.map_or("GET", |_| "POST"), url, response.code)?;
+    Ok(response)
+}
+
+/// Appends the request to the file in `ADVENT_REQUEST_LOG`, so that tests
+/// can count the requests made.
+fn log(method: &str, url: &str, code: u32) -> Result<()> {
+    let Some(path) = env::var_os("ADVENT_REQUEST_LOG") else {
+        return Ok(());
+    };
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{method} {url} {code}")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors() {
+        let url = "https://adventofcode.com/2024/day/6/input";
+        assert_eq!(check(url, 200, "1\n".into()).unwrap(), "1\n");
+        let err = check(
+            url,
+            404,
+            "Please don't repeatedly request this endpoint".into(),
+        );
+        let err = err.unwrap_err();
+        assert!(err.to_string().contains("not unlocked yet"));
+        assert!(err.downcast_ref::<NotUnlocked>().is_some());
+        let err = check(url, 400, "Please log in to get your puzzle input.".into());
+        assert!(err.unwrap_err().to_string().contains("not logged in"));
+        let err = check("https://adventofcode.com/settings", 500, String::new());
+        assert!(err.unwrap_err().to_string().contains("HTTP 500"));
+        let url = "https://adventofcode.com/2024/day/6/answer";
+        let err = check(url, 200, "<p>Please log in.</p>".into());
+        assert!(err.unwrap_err().to_string().contains("not logged in"));
+    }
+
+    #[test]
+    fn puzzle_mentioning_login() {
+        let url = "https://adventofcode.com/2024/day/6";
+        let page = include_str!("../tests/fixtures/2024/06-part1.html").replace(
+            "</article>",
+            "<p>The terminal keeps asking you to please log in.</p></article>",
+        );
+        assert_eq!(check(url, 200, page.clone()).unwrap(), page);
+    }
+
+    #[test]
+    fn cache_paths() {
+        let url = "https://adventofcode.com/2024/day/6/input";
+        assert_ne!(cache_path(url, "a"), cache_path(url, "b"));
+        assert_eq!(cache_path(url, "a"), cache_path(url, "a"));
+    }
+}
@@ -0,0 +1,341 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::bench::Format;
+use crate::workspace_dir;
+
+/// Settings read from `advent.toml` in the workspace and the user config.
+///
+/// Workspace values take precedence over user values, and command line flags
+/// and environment variables take precedence over both.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// The session cookie.
+    pub session: Option<String>,
+    /// A shell command that prints the session cookie, e.g. to read it from a
+    /// password manager.
+    pub session_command: Option<String>,
+    /// The browser `open` uses, the system default if not set.
+    pub browser: Option<String>,
+    /// The year used when `-y/--year` is not given.
+    pub year: Option<u32>,
+    /// Where puzzle inputs are stored, relative to the workspace.
+    pub input_dir: Option<PathBuf>,
+    /// The template `new` uses without `--template`, a name or a path
+    /// relative to the workspace.
+    pub template: Option<String>,
+    /// Named templates, as paths relative to the workspace.
+    pub templates: BTreeMap<String, PathBuf>,
+    /// The output format of `bench`.
+    pub format: Option<Format>,
+    pub base_url: Option<String>,
+    pub user_agent: Option<String>,
+    /// The passphrase of the encrypted input store, only allowed in the user
+    /// config.
+    pub input_key: Option<String>,
+    /// A shell command that prints the passphrase of the encrypted input
+    /// store.
+    pub input_key_command: Option<String>,
+    /// The minimum number of seconds between requests to the site.
+    pub request_interval: Option<f64>,
+    /// The profile used when `-p/--profile` is not given.
+    pub profile: Option<String>,
+    /// Named accounts, each with its own session and inputs.
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// The settings of a named account.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Profile {
+    pub session: Option<String>,
+    pub session_command: Option<String>,
+}
+
+impl Config {
+    fn parse(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)?;
+        toml::from_str(&contents).with_context(|| format!("failed to parse `{}`", path.display()))
+    }
+
+    /// Rejects settings that must not be committed with the workspace.
+    fn check_workspace(&self) -> Result<()> {
+        if self.session.is_some() {
+            bail!(
+                "`session` must not be set in the workspace `advent.toml`, which is committed; \
+                 set it in the user config or use `session-command`"
+            );
+        }
+        if let Some(name) = self
+            .profiles
+            .iter()
+            .find_map(|(name, profile)| profile.session.as_ref().map(|_| name))
+        {
+            bail!(
+                "`profiles.{name}.session` must not be set in the workspace `advent.toml`, which \
+                 is committed; set it in the user config or use `session-command`"
+            );
+        }
+        if self.input_key.is_some() {
+            bail!(
+                "`input-key` must not be set in the workspace `advent.toml`, which is committed \
+                 next to the encrypted inputs; set it in the user config or use `input-key-command`"
+            );
+        }
+        Ok(())
+    }
+
+    /// Fills in the settings missing from `self` from `other`.
+    fn or(self, other: Self) -> Self {
+        Self {
+            session: self.session.or(other.session),
+            session_command: self.session_command.or(other.session_command),
+            browser: self.browser.or(other.browser),
+            year: self.year.or(other.year),
+            input_dir: self.input_dir.or(other.input_dir),
+            template: self.template.or(other.template),
+            templates: other.templates.into_iter().chain(self.templates).collect(),
+            format: self.format.or(other.format),
+            base_url: self.base_url.or(other.base_url),
+            user_agent: self.user_agent.or(other.user_agent),
+            input_key: self.input_key.or(other.input_key),
+            input_key_command: self.input_key_command.or(other.input_key_command),
+            request_interval: self.request_interval.or(other.request_interval),
+            profile: self.profile.or(other.profile),
+            profiles: other.profiles.into_iter().chain(self.profiles).collect(),
+        }
+    }
+
+    /// Returns the session cookie of the selected profile, or from
+    /// `ADVENT_SESSION` or the config without one.
+    pub fn session(&self) -> Result<String> {
+        if let Some(name) = profile() {
+            return self.profile_session(name);
+        }
+        if let Ok(session) = env::var("ADVENT_SESSION") {
+            return Ok(session);
+        }
+        secret(self.session.as_deref(), self.session_command.as_deref())
+            .context("`ADVENT_SESSION` must be set, or `session` in `advent.toml`")
+    }
+
+    /// Returns the passphrase of the encrypted input store, from
+    /// `ADVENT_INPUT_KEY` or the config, if one is set.
+    pub fn input_key(&self) -> Result<Option<String>> {
+        if let Ok(key) = env::var("ADVENT_INPUT_KEY") {
+            return Ok(Some(key));
+        }
+        if self.input_key.is_none() && self.input_key_command.is_none() {
+            return Ok(None);
+        }
+        secret(self.input_key.as_deref(), self.input_key_command.as_deref()).map(Some)
+    }
+
+    /// Returns the session cookie of a named profile.
+    pub fn profile_session(&self, name: &str) -> Result<String> {
+        let profile = self.profile_named(name)?;
+        secret(
+            profile.session.as_deref(),
+            profile.session_command.as_deref(),
+        )
+        .with_context(|| format!("no session for profile `{name}`"))
+    }
+
+    pub fn profile_named(&self, name: &str) -> Result<&Profile> {
+        self.profiles
+            .get(name)
+            .with_context(|| format!("no profile `{name}` in `advent.toml`"))
+    }
+}
+
+/// Returns the given secret, or runs the command that prints it.
+fn secret(value: Option<&str>, command: Option<&str>) -> Result<String> {
+    if let Some(value) = value {
+        return Ok(value.to_owned());
+    }
+    let Some(command) = command else {
+        bail!("neither a value nor a command is set");
+    };
+    let (shell, flag) = match cfg!(windows) {
+        true => ("cmd", "/C"),
+        false => ("sh", "-c"),
+    };
+    let output = process::Command::new(shell)
+        .args([flag, command])
+        .stderr(process::Stdio::inherit())
+        .output()
+        .with_context(|| format!("failed to run `{command}`"))?;
+    if !output.status.success() {
+        bail!("`{command}` failed with {}", output.status);
+    }
+    let value = String::from_utf8(output.stdout)?.trim().to_owned();
+    if value.is_empty() {
+        bail!("`{command}` printed nothing");
+    }
+    Ok(value)
+}
+
+static PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+/// Selects the profile, the `-p/--profile` flag takes precedence over
+/// `ADVENT_PROFILE` and `profile` in the config.
+pub fn select_profile(flag: Option<String>) {
+    let profile = flag
+        .or_else(|| env::var("ADVENT_PROFILE").ok())
+        .filter(|p| !p.is_empty())
+        .or_else(|| get().profile.clone());
+    PROFILE.set(profile).expect("profile already selected");
+}
+
+/// Returns the selected profile, if any.
+pub fn profile() -> Option<&'static str> {
+    PROFILE.get().and_then(Option::as_deref)
+}
+
+/// The user config, `$XDG_CONFIG_HOME/advent/config.toml` or
+/// `~/.config/advent/config.toml`.
+fn user_path() -> Option<PathBuf> {
+    let dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(dir.join("advent/config.toml"))
+}
+
+fn load() -> Result<Config> {
+    let workspace = Config::parse(&workspace_dir().join("advent.toml"))?;
+    workspace.check_workspace()?;
+    let user = match user_path() {
+        Some(path) => Config::parse(&path)?,
+        None => Config::default(),
+    };
+    Ok(workspace.or(user))
+}
+
+/// Returns the merged configuration, loading it on first use.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        load().unwrap_or_else(|err| {
+            crate::error("Error", format!("{err:#}"));
+            process::exit(1)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_merge() {
+        let workspace: Config = toml::from_str(
+            r#"
+year = 2025
+input-dir = "inputs"
+format = "json"
+"#,
+        )
+        .unwrap();
+        let user: Config = toml::from_str(
+            r#"
+session-command = "pass show aoc"
+browser = "chromium"
+year = 2024
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            workspace.or(user),
+            Config {
+                session_command: Some("pass show aoc".into()),
+                browser: Some("chromium".into()),
+                year: Some(2025),
+                input_dir: Some("inputs".into()),
+                format: Some(Format::Json),
+                ..Config::default()
+            }
+        );
+    }
+
+    #[test]
+    fn input_key_only_in_user_config() {
+        let config: Config = toml::from_str("input-key = \"hunter2\"").unwrap();
+        let err = config.check_workspace().unwrap_err();
+        assert!(err.to_string().contains("user config"));
+        let config: Config = toml::from_str("input-key-command = \"pass aoc\"").unwrap();
+        assert!(config.check_workspace().is_ok());
+    }
+
+    #[test]
+    fn session_only_in_user_config() {
+        let config: Config = toml::from_str("session = \"533\"").unwrap();
+        let err = config.check_workspace().unwrap_err();
+        assert!(err.to_string().contains("user config"));
+        let config: Config = toml::from_str("[profiles.alice]\nsession = \"533\"").unwrap();
+        let err = config.check_workspace().unwrap_err();
+        assert!(err.to_string().contains("`profiles.alice.session`"));
+        let config: Config = toml::from_str(
+            "session-command = \"pass aoc\"\n[profiles.alice]\nsession-command = \"pass a\"",
+        )
+        .unwrap();
+        assert!(config.check_workspace().is_ok());
+    }
+
+    #[test]
+    fn unknown_key() {
+        assert!(toml::from_str::<Config>("sesion = \"x\"").is_err());
+    }
+
+    #[test]
+    fn profiles() {
+        let workspace: Config = toml::from_str(
+            r#"
+profile = "alice"
+
+[profiles.alice]
+session-command = "echo a"
+"#,
+        )
+        .unwrap();
+        let user: Config = toml::from_str(
+            r#"
+[profiles.alice]
+session = "ignored"
+
+[profiles.bob]
+session-command = "echo b"
+"#,
+        )
+        .unwrap();
+        let config = workspace.or(user);
+        assert_eq!(config.profile.as_deref(), Some("alice"));
+        assert_eq!(
+            config.profile_named("alice").unwrap(),
+            &Profile {
+                session: None,
+                session_command: Some("echo a".to_owned()),
+            }
+        );
+        assert_eq!(
+            config
+                .profile_named("bob")
+                .unwrap()
+                .session_command
+                .as_deref(),
+            Some("echo b")
+        );
+        assert!(config.profile_named("carol").is_err());
+    }
+}
@@ -0,0 +1,149 @@
+//! Authenticated encryption for the committed input store.
+//!
+//! The key is derived from the passphrase with Argon2id and a random salt kept
+//! in the store. Each file is encrypted with ChaCha20-Poly1305 under a random
+//! nonce, with its label as associated data so that files cannot be swapped.
+
+use anyhow::{Result, anyhow, bail};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+
+const MAGIC: &[u8] = b"AOCENC2\n";
+const NONCE_LEN: usize = 12;
+pub const SALT_LEN: usize = 16;
+
+/// The key derived from a passphrase.
+#[derive(Clone)]
+pub struct Key {
+    cipher: ChaCha20Poly1305,
+}
+
+impl Key {
+    /// Derives the key with the default Argon2id parameters, 19 MiB of
+    /// memory and two passes.
+    pub fn derive(passphrase: &str, salt: &[u8]) -> Result<Self> {
+        Self::derive_with(passphrase, salt, Params::default())
+    }
+
+    /// Derives a key cheaply, for unit tests which run unoptimised.
+    #[cfg(test)]
+    pub fn weak(passphrase: &str) -> Self {
+        let params = Params::new(Params::MIN_M_COST, Params::MIN_T_COST, 1, None).unwrap();
+        Self::derive_with(passphrase, &[0; SALT_LEN], params).unwrap()
+    }
+
+    fn derive_with(passphrase: &str, salt: &[u8], params: Params) -> Result<Self> {
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|err| anyhow!("failed to derive the key: {err}"))?;
+        Ok(Self {
+            cipher: ChaCha20Poly1305::new(&key.into()),
+        })
+    }
+
+    /// Encrypts data, binding it to the label so that files cannot be swapped.
+    pub fn encrypt(&self, label: &str, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: plaintext,
+            aad: label.as_bytes(),
+        };
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, payload)
+            .expect("input is too large to encrypt");
+        [MAGIC, &nonce, &ciphertext].concat()
+    }
+
+    /// Decrypts data, failing if it was modified or the key is wrong.
+    pub fn decrypt(&self, label: &str, data: &[u8]) -> Result<Vec<u8>> {
+        let Some(rest) = data.strip_prefix(MAGIC) else {
+            bail!("not an encrypted input");
+        };
+        if rest.len() < NONCE_LEN {
+            bail!("encrypted input is truncated");
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let payload = Payload {
+            msg: ciphertext,
+            aad: label.as_bytes(),
+        };
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map_err(|_| anyhow!("wrong key, or the encrypted input was modified"))
+    }
+}
+
+/// Returns a new random salt for a store.
+pub fn salt() -> [u8; SALT_LEN] {
+    let mut salt = [0; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let key = Key::weak("correct horse battery staple");
+        for plaintext in [&b""[..], b"1\n", &[b'#'; 1000]] {
+            let encrypted = key.encrypt("2024/06.txt", plaintext);
+            assert_eq!(
+                encrypted.len(),
+                MAGIC.len() + NONCE_LEN + plaintext.len() + 16
+            );
+            if !plaintext.is_empty() {
+                assert_ne!(
+                    &encrypted[MAGIC.len() + NONCE_LEN..][..plaintext.len()],
+                    plaintext
+                );
+            }
+            assert_eq!(key.decrypt("2024/06.txt", &encrypted).unwrap(), plaintext);
+            assert_ne!(key.encrypt("2024/06.txt", plaintext), encrypted);
+        }
+    }
+
+    #[test]
+    fn salted() {
+        let key = |salt: &[u8]| {
+            let params = Params::new(Params::MIN_M_COST, Params::MIN_T_COST, 1, None).unwrap();
+            Key::derive_with("hunter2", salt, params).unwrap()
+        };
+        let encrypted = key(&[1; SALT_LEN]).encrypt("2024/06.txt", b"1\n");
+        assert!(
+            key(&[1; SALT_LEN])
+                .decrypt("2024/06.txt", &encrypted)
+                .is_ok()
+        );
+        assert!(
+            key(&[2; SALT_LEN])
+                .decrypt("2024/06.txt", &encrypted)
+                .is_err()
+        );
+        assert_ne!(salt(), salt());
+    }
+
+    #[test]
+    fn rejects_tampering() {
+        let key = Key::weak("correct horse battery staple");
+        let encrypted = key.encrypt("2024/06.txt", b"....#.....\n");
+
+        let mut modified = encrypted.clone();
+        *modified.last_mut().unwrap() ^= 1;
+        assert!(key.decrypt("2024/06.txt", &modified).is_err());
+        assert!(key.decrypt("2024/06.txt", &encrypted[..20]).is_err());
+        assert!(key.decrypt("2024/06.txt", &encrypted[..10]).is_err());
+        assert!(key.decrypt("2024/07.txt", &encrypted).is_err());
+        assert!(
+            Key::weak("wrong")
+                .decrypt("2024/06.txt", &encrypted)
+                .is_err()
+        );
+        assert!(key.decrypt("2024/06.txt", b"....#.....\n").is_err());
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::html::{self, Element, Node};
+
+/// A `<pre><code>` block found in a puzzle description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    /// The puzzle part whose description contains the block.
+    pub part: u32,
+    pub text: String,
+    /// Whether the preceding paragraph mentions an example.
+    pub after_example: bool,
+}
+
+/// Everything that looks like an example on a puzzle page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Extracted {
+    /// All code blocks, in document order.
+    pub candidates: Vec<Candidate>,
+    /// The last emphasized code of each part, which is the example answer.
+    pub answers: Vec<Option<String>>,
+}
+
+/// An example input together with its expected answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Answers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+/// Finds the code blocks and example answers on a puzzle page.
+pub fn extract(page: &str) -> Extracted {
+    let nodes = html::parse(page);
+    let articles = html::find_all(&nodes, &|e| e.name == "article" && e.has_class("day-desc"));
+
+    let mut extracted = Extracted::default();
+    for (part, article) in (1..).zip(articles) {
+        let mut after_example = false;
+        for block in blocks(article) {
+            match block.name.as_str() {
+                "p" => after_example = block.text().to_lowercase().contains("example"),
+                "pre" => {
+                    extracted.candidates.push(Candidate {
+                        part,
+                        text: block.text(),
+                        after_example,
+                    });
+                    after_example = false;
+                }
+                _ => {}
+            }
+        }
+
+        let answers = article.find_all(&|e| {
+            (e.name == "code" && !e.find_all(&|e| e.name == "em").is_empty())
+                || (e.name == "em" && is_only_code(e))
+        });
+        extracted
+            .answers
+            .push(answers.last().map(|e| e.text().trim().to_owned()));
+    }
+    extracted
+}
+
+/// Returns the paragraphs and preformatted blocks of an article in order.
+fn blocks(e: &Element) -> Vec<&Element> {
+    html::find_all(&e.children, &|e| e.name == "p" || e.name == "pre")
+}
+
+fn is_only_code(e: &Element) -> bool {
+    matches!(&e.children[..], [Node::Element(code)] if code.name == "code")
+}
+
+impl Extracted {
+    /// Returns the indexes of the blocks that might be the example of a part.
+    fn likely(&self, part: u32) -> Vec<usize> {
+        let in_part: Vec<_> = (0..self.candidates.len())
+            .filter(|&i| self.candidates[i].part == part)
+            .collect();
+        let likely: Vec<_> = in_part
+            .iter()
+            .copied()
+            .filter(|&i| self.candidates[i].after_example)
+            .collect();
+        if likely.is_empty() && part == 1 {
+            return in_part.into_iter().take(1).collect();
+        }
+        likely
+    }
+
+    /// Returns whether the heuristics found several possible part 1 examples.
+    pub fn is_ambiguous(&self) -> bool {
+        self.likely(1).len() > 1
+    }
+
+    /// Chooses the examples, `pick` is the 1-based index of the code block to
+    /// use as the part 1 example instead of the heuristic choice.
+    pub fn examples(&self, pick: Option<usize>) -> Result<Vec<Example>> {
+        let first = match pick {
+            Some(n) if (1..=self.candidates.len()).contains(&n) => n - 1,
+            Some(n) => bail!("there is no code block {n}"),
+            None => match self.likely(1).first() {
+                Some(&i) => i,
+                None => return Ok(Vec::new()),
+            },
+        };
+        let answer = |part: usize| self.answers.get(part - 1).cloned().flatten();
+
+        let mut examples = vec![Example {
+            input: self.candidates[first].text.clone(),
+            part1: answer(1),
+            part2: None,
+        }];
+        match self.likely(2).first() {
+            Some(&i) if self.candidates[i].text != examples[0].input => examples.push(Example {
+                input: self.candidates[i].text.clone(),
+                part1: None,
+                part2: answer(2),
+            }),
+            _ => examples[0].part2 = answer(2),
+        }
+        Ok(examples)
+    }
+}
+
+pub fn dir(workspace_dir: &Path, year: u32, day: u32) -> PathBuf {
+    workspace_dir.join(format!("examples/{year:04}/{day:02}"))
+}
+
+/// Writes the examples to `N.txt` files with their answers in `answers.toml`.
+pub fn save(dir: &Path, examples: &[Example]) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut answers = BTreeMap::new();
+    for (n, example) in (1..).zip(examples) {
+        fs::write(dir.join(format!("{n}.txt")), &example.input)?;
+        answers.insert(
+            n.to_string(),
+            Answers {
+                part1: example.part1.clone(),
+                part2: example.part2.clone(),
+            },
+        );
+    }
+    fs::write(dir.join("answers.toml"), toml::to_string(&answers)?)?;
+    Ok(())
+}
+
+/// Reads the examples stored for a day.
+pub fn load(dir: &Path) -> Result<Vec<Example>> {
+    let path = dir.join("answers.toml");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let answers: BTreeMap<String, Answers> = toml::from_str(&fs::read_to_string(path)?)?;
+    let mut examples = Vec::new();
+    for n in 1.. {
+        let Some(Answers { part1, part2 }) = answers.get(&n.to_string()) else {
+            break;
+        };
+        examples.push(Example {
+            input: fs::read_to_string(dir.join(format!("{n}.txt")))?,
+            part1: part1.clone(),
+            part2: part2.clone(),
+        });
+    }
+    Ok(examples)
+}
+
+/// Renders the body of an `examples()` test that loads the example files.
+pub fn render_test(year: u32, day: u32, examples: &[Example]) -> String {
+    let mut tests = Vec::new();
+    for (n, example) in (1..).zip(examples) {
+        let asserts: Vec<_> = [("part1", &example.part1), ("part2", &example.part2)]
+            .into_iter()
+            .filter_map(|(f, answer)| Some((f, literal(answer.as_ref()?))))
+            .collect();
+        let mut test = format!(
+            "        let input = parse_input(include_str!(\"../examples/{year:04}/{day:02}/{n}.txt\")).unwrap();\n"
+        );
+        for (i, (f, expected)) in asserts.iter().enumerate() {
+            let arg = if i + 1 < asserts.len() {
+                "input.clone()"
+            } else {
+                "input"
+            };
+            test += &format!("        assert_eq!({f}({arg}), {expected});\n");
+        }
+        tests.push(test);
+    }
+    tests.join("\n")
+}
+
+/// Formats an answer as a Rust literal, numbers are left unquoted.
+pub fn literal(answer: &str) -> String {
+    if answer.parse::<i64>().is_ok() {
+        answer.to_owned()
+    } else {
+        format!("{answer:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_one() {
+        let extracted = extract(include_str!("../tests/fixtures/2024/06-part1.html"));
+        assert_eq!(extracted.candidates.len(), 1);
+        assert!(extracted.candidates[0].after_example);
+        assert!(!extracted.is_ambiguous());
+
+        let examples = extracted.examples(None).unwrap();
+        assert_eq!(
+            examples,
+            [Example {
+                input: "....#.....\n....^....#\n..........\n".into(),
+                part1: Some("41".into()),
+                part2: None,
+            }]
+        );
+        assert_eq!(
+            render_test(2024, 6, &examples),
+            "        let input = parse_input(include_str!(\"../examples/2024/06/1.txt\")).unwrap();\n        \
+             assert_eq!(part1(input), 41);\n"
+        );
+    }
+
+    #[test]
+    fn both_parts() {
+        let extracted = extract(include_str!("../tests/fixtures/2024/06-part2.html"));
+        let examples = extracted.examples(None).unwrap();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].part1.as_deref(), Some("41"));
+        assert_eq!(examples[0].part2.as_deref(), Some("6"));
+    }
+
+    #[test]
+    fn separate_part_two_example() {
+        let page = "\
+<article class=\"day-desc\"><p>Some text:</p><pre><code>not it</code></pre>
+<p>For example:</p><pre><code>1 2 3</code></pre>
+<p>Here the answer is <em><code>6</code></em>.</p></article>
+<article class=\"day-desc\"><p>For example:</p><pre><code>a b</code></pre>
+<p>Now the answer is <code><em>ab</em></code>.</p></article>";
+        let extracted = extract(page);
+        assert_eq!(extracted.candidates.len(), 3);
+        let examples = extracted.examples(None).unwrap();
+        assert_eq!(
+            examples,
+            [
+                Example {
+                    input: "1 2 3".into(),
+                    part1: Some("6".into()),
+                    part2: None,
+                },
+                Example {
+                    input: "a b".into(),
+                    part1: None,
+                    part2: Some("ab".into()),
+                },
+            ]
+        );
+        assert_eq!(extracted.examples(Some(1)).unwrap()[0].input, "not it");
+        assert!(extracted.examples(Some(4)).is_err());
+        assert!(render_test(2024, 1, &examples).contains("assert_eq!(part2(input), \"ab\");"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = std::env::temp_dir().join(format!("advent-examples-{}", std::process::id()));
+        let examples = vec![Example {
+            input: "1\n2\n".into(),
+            part1: Some("3".into()),
+            part2: Some("x".into()),
+        }];
+        save(&dir, &examples).unwrap();
+        assert_eq!(load(&dir).unwrap(), examples);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,284 @@
+//! A tiny HTML parser, just enough for the pages served by Advent of Code.
+
+/// A node in the parsed document tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+/// Elements whose content is skipped entirely.
+const RAW_TEXT: &[&str] = &["script", "style"];
+
+impl Element {
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn has_class(&self, class: &str) -> bool {
+        self.attr("class")
+            .is_some_and(|c| c.split_whitespace().any(|c| c == class))
+    }
+
+    /// Returns the concatenated text of all descendants.
+    pub fn text(&self) -> String {
+        let mut out = String::new();
+        for child in &self.children {
+            match child {
+                Node::Text(text) => out.push_str(text),
+                Node::Element(e) => out.push_str(&e.text()),
+            }
+        }
+        out
+    }
+
+    /// Returns all descendant elements, in document order, that match.
+    pub fn find_all<'a>(&'a self, pred: &impl Fn(&Element) -> bool) -> Vec<&'a Element> {
+        find_all(&self.children, pred)
+    }
+}
+
+/// Returns all elements in the tree, in document order, that match.
+pub fn find_all<'a>(nodes: &'a [Node], pred: &impl Fn(&Element) -> bool) -> Vec<&'a Element> {
+    let mut found = Vec::new();
+    for node in nodes {
+        if let Node::Element(e) = node {
+            if pred(e) {
+                found.push(e);
+            }
+            found.extend(e.find_all(pred));
+        }
+    }
+    found
+}
+
+/// Parses an HTML document into a tree of nodes.
+///
+/// This is forgiving: unknown closing tags are ignored and unclosed elements
+/// are closed when one of their ancestors is.
+pub fn parse(html: &str) -> Vec<Node> {
+    // A stack of open elements, the root is a dummy element
+    let mut stack = vec![Element {
+        name: String::new(),
+        attrs: Vec::new(),
+        children: Vec::new(),
+    }];
+
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(i) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        push_text(&mut stack, &rest[..i]);
+        rest = &rest[i..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |j| &comment[j + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |j| &rest[j + 1..]);
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let end = tag.find('>').unwrap_or(tag.len());
+            let name = tag[..end].trim().to_ascii_lowercase();
+            rest = tag.get(end + 1..).unwrap_or("");
+            if stack[1..].iter().any(|e| e.name == name) {
+                loop {
+                    let e = stack.pop().unwrap();
+                    let done = e.name == name;
+                    stack.last_mut().unwrap().children.push(Node::Element(e));
+                    if done {
+                        break;
+                    }
+                }
+            }
+        } else {
+            let Some((element, self_closing, after)) = parse_tag(&rest[1..]) else {
+                push_text(&mut stack, "<");
+                rest = &rest[1..];
+                continue;
+            };
+            rest = after;
+            if RAW_TEXT.contains(&element.name.as_str()) {
+                let close = format!("</{}", element.name);
+                rest = rest.find(&close).map_or("", |j| &rest[j..]);
+                rest = rest.find('>').map_or("", |j| &rest[j + 1..]);
+                continue;
+            }
+            if self_closing || VOID.contains(&element.name.as_str()) {
+                stack
+                    .last_mut()
+                    .unwrap()
+                    .children
+                    .push(Node::Element(element));
+            } else {
+                stack.push(element);
+            }
+        }
+    }
+
+    while stack.len() > 1 {
+        let e = stack.pop().unwrap();
+        stack.last_mut().unwrap().children.push(Node::Element(e));
+    }
+    stack.pop().unwrap().children
+}
+
+fn push_text(stack: &mut [Element], raw: &str) {
+    if raw.is_empty() {
+        return;
+    }
+    let text = decode_entities(raw);
+    let children = &mut stack.last_mut().unwrap().children;
+    match children.last_mut() {
+        Some(Node::Text(prev)) => prev.push_str(&text),
+        _ => children.push(Node::Text(text)),
+    }
+}
+
+/// Parses the inside of an opening tag, returning the element, whether it
+/// was self-closing and the remaining input.
+fn parse_tag(s: &str) -> Option<(Element, bool, &str)> {
+    let name_end = s.find(|c: char| c.is_whitespace() || c == '>' || c == '/')?;
+    let name = s[..name_end].to_ascii_lowercase();
+    if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut attrs = Vec::new();
+    let mut rest = &s[name_end..];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return Some((element(name, attrs), true, after));
+        } else if let Some(after) = rest.strip_prefix('>') {
+            return Some((element(name, attrs), false, after));
+        } else if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            continue;
+        } else if rest.is_empty() {
+            return None;
+        }
+
+        let key_end = rest.find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')?;
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+                let (value, after) = match after.chars().next()? {
+                    q @ ('"' | '\'') => {
+                        let end = after[1..].find(q)? + 1;
+                        (&after[1..end], &after[end + 1..])
+                    }
+                    _ => {
+                        let end = after
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .unwrap_or(after.len());
+                        (&after[..end], &after[end..])
+                    }
+                };
+                rest = after;
+                decode_entities(value)
+            }
+            None => String::new(),
+        };
+        attrs.push((key, value));
+    }
+}
+
+fn element(name: String, attrs: Vec<(String, String)>) -> Element {
+    Element {
+        name,
+        attrs,
+        children: Vec::new(),
+    }
+}
+
+/// Decodes the character references that show up in puzzle pages.
+pub fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let decoded = rest.find(';').filter(|&j| j <= 10).and_then(|j| {
+            let entity = &rest[1..j];
+            let c = match entity {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => '\u{a0}',
+                _ => {
+                    let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, j))
+        });
+        match decoded {
+            Some((c, j)) => {
+                out.push(c);
+                rest = &rest[j + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree() {
+        let nodes = parse(
+            "<!DOCTYPE html><html><head><script>if (a < b) {}</script></head>\
+             <body><p class=\"x y\">Hello <em>world</em>&lt;3<br/>done</p><!-- c --></body></html>",
+        );
+        let ps = find_all(&nodes, &|e| e.name == "p");
+        assert_eq!(ps.len(), 1);
+        assert!(ps[0].has_class("y"));
+        assert_eq!(ps[0].text(), "Hello world<3done");
+        assert!(find_all(&nodes, &|e| e.name == "script").is_empty());
+    }
+
+    #[test]
+    fn unclosed() {
+        let nodes = parse("<ul><li>one<li>two</ul><p>after</p>");
+        let ul = find_all(&nodes, &|e| e.name == "ul");
+        assert_eq!(ul[0].text(), "onetwo");
+        assert_eq!(find_all(&nodes, &|e| e.name == "p")[0].text(), "after");
+    }
+
+    #[test]
+    fn entities() {
+        assert_eq!(
+            decode_entities("a &amp; b &#x41;&#66; &bogus; &"),
+            "a & b AB &bogus; &"
+        );
+    }
+}
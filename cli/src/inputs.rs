@@ -0,0 +1,326 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::crypto::{self, Key};
+
+/// What we know about a downloaded input, stored next to it in
+/// `{day}.meta.toml` so that modified or truncated files can be detected.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Meta {
+    /// When the input was downloaded, in RFC 3339.
+    pub downloaded: String,
+    pub sha256: String,
+    pub length: usize,
+}
+
+/// The result of checking an input against its metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Ok,
+    /// The input predates metadata, or the metadata was removed.
+    NoMeta,
+    Mismatch {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+pub fn meta_path(input: &Path) -> PathBuf {
+    input.with_extension("meta.toml")
+}
+
+/// Converts Windows line endings and adds a missing trailing newline.
+///
+/// Returns the normalised input and a description of each fix applied.
+pub fn normalize(text: &str) -> (String, Vec<&'static str>) {
+    let mut fixes = Vec::new();
+    let mut text = text.to_owned();
+    if text.contains("\r\n") {
+        text = text.replace("\r\n", "\n");
+        fixes.push("converted Windows line endings");
+    }
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+        fixes.push("added a missing trailing newline");
+    }
+    (text, fixes)
+}
+
+/// Normalises and writes an input together with its metadata.
+///
+/// Returns the fixes applied by [`normalize`].
+pub fn save(input: &Path, text: &str, now: OffsetDateTime) -> Result<Vec<&'static str>> {
+    let (text, fixes) = normalize(text);
+    let meta = Meta {
+        downloaded: now.replace_nanosecond(0)?.format(&Rfc3339)?,
+        sha256: sha256_hex(text.as_bytes()),
+        length: text.len(),
+    };
+    fs::create_dir_all(input.parent().unwrap())?;
+    fs::write(input, &text)?;
+    fs::write(meta_path(input), toml::to_string(&meta)?)?;
+    Ok(fixes)
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Checks an input against its metadata.
+pub fn verify(input: &Path) -> Result<Check> {
+    let meta_path = meta_path(input);
+    if !meta_path.exists() {
+        return Ok(Check::NoMeta);
+    }
+    let meta: Meta = toml::from_str(&fs::read_to_string(&meta_path)?)
+        .with_context(|| format!("failed to parse `{}`", meta_path.display()))?;
+    let data = fs::read(input)?;
+    if data.len() == meta.length && sha256_hex(&data) == meta.sha256 {
+        Ok(Check::Ok)
+    } else {
+        Ok(Check::Mismatch {
+            expected: meta.length,
+            actual: data.len(),
+        })
+    }
+}
+
+/// What happened to a file when locking or unlocking the encrypted store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sync {
+    Written,
+    Unchanged,
+    /// The destination differs and was kept, when unlocking over local edits.
+    Kept,
+}
+
+/// Returns the paths of all files under a directory, relative to it.
+fn files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut stack = vec![PathBuf::new()];
+    while let Some(rel) = stack.pop() {
+        let Ok(entries) = fs::read_dir(dir.join(&rel)) else {
+            continue;
+        };
+        for entry in entries {
+            let entry = entry?;
+            let path = rel.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                stack.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The label an encrypted file is bound to, its path with `/` separators.
+fn label(rel: &Path) -> String {
+    let parts: Vec<_> = rel.iter().map(|p| p.to_string_lossy()).collect();
+    parts.join("/")
+}
+
+fn encrypted_path(store: &Path, rel: &Path) -> PathBuf {
+    let mut path = store.join(rel).into_os_string();
+    path.push(".enc");
+    path.into()
+}
+
+/// Derives the key of the store from the passphrase and the salt in the
+/// store, creating a random salt for a new store.
+pub fn key(store: &Path, passphrase: &str) -> Result<Key> {
+    let path = store.join("salt");
+    let salt = match fs::read_to_string(&path) {
+        Ok(hex) => (0..hex.trim().len())
+            .step_by(2)
+            .map(|i| {
+                hex.trim()
+                    .get(i..i + 2)
+                    .and_then(|b| u8::from_str_radix(b, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .filter(|salt| salt.len() == crypto::SALT_LEN)
+            .with_context(|| format!("`{}` is not a valid salt", path.display()))?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            let salt = crypto::salt();
+            fs::create_dir_all(store)?;
+            fs::write(&path, format!("{}\n", hex(&salt)))?;
+            salt.to_vec()
+        }
+        Err(err) => return Err(err.into()),
+    };
+    Key::derive(passphrase, &salt)
+}
+
+/// Encrypts every input and its metadata into the store.
+///
+/// Encryption uses a random nonce, so files whose input did not change are
+/// left as they are instead of being encrypted again.
+pub fn lock(input_dir: &Path, store: &Path, key: &Key) -> Result<Vec<(PathBuf, Sync)>> {
+    let mut synced = Vec::new();
+    for rel in files(input_dir)? {
+        let (label, plaintext) = (label(&rel), fs::read(input_dir.join(&rel))?);
+        let path = encrypted_path(store, &rel);
+        let existing = fs::read(&path).ok();
+        let sync =
+            if existing.is_some_and(|e| key.decrypt(&label, &e).is_ok_and(|p| p == plaintext)) {
+                Sync::Unchanged
+            } else {
+                fs::create_dir_all(path.parent().unwrap())?;
+                fs::write(&path, key.encrypt(&label, &plaintext))?;
+                Sync::Written
+            };
+        synced.push((rel, sync));
+    }
+    Ok(synced)
+}
+
+/// Decrypts every file in the store, keeping inputs that were edited locally.
+pub fn unlock(store: &Path, input_dir: &Path, key: &Key) -> Result<Vec<(PathBuf, Sync)>> {
+    let mut synced = Vec::new();
+    for rel in files(store)? {
+        let Some(rel) = rel.to_str().and_then(|r| r.strip_suffix(".enc")) else {
+            continue;
+        };
+        let rel = PathBuf::from(rel);
+        let sync = unlock_file(store, input_dir, &rel, key)?;
+        synced.push((rel, sync));
+    }
+    Ok(synced)
+}
+
+/// Decrypts a single file from the store, if it is there.
+pub fn unlock_file(store: &Path, input_dir: &Path, rel: &Path, key: &Key) -> Result<Sync> {
+    let encrypted = fs::read(encrypted_path(store, rel))?;
+    let plaintext = key
+        .decrypt(&label(rel), &encrypted)
+        .with_context(|| format!("failed to decrypt `{}`", label(rel)))?;
+    let path = input_dir.join(rel);
+    match fs::read(&path) {
+        Ok(existing) if existing == plaintext => Ok(Sync::Unchanged),
+        Ok(_) => Ok(Sync::Kept),
+        Err(_) => {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, plaintext)?;
+            Ok(Sync::Written)
+        }
+    }
+}
+
+/// Returns whether the store has an encrypted copy of the file.
+pub fn is_locked(store: &Path, rel: &Path) -> bool {
+    encrypted_path(store, rel).exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes() {
+        assert_eq!(normalize("1\n2\n"), ("1\n2\n".into(), vec![]));
+        assert_eq!(
+            normalize("1\r\n2"),
+            (
+                "1\n2\n".into(),
+                vec![
+                    "converted Windows line endings",
+                    "added a missing trailing newline"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn detects_changes() {
+        let dir = std::env::temp_dir().join(format!("advent-inputs-{}", std::process::id()));
+        let input = dir.join("2024/06.txt");
+        let fixes = save(&input, "1\r\n2\r\n", OffsetDateTime::UNIX_EPOCH).unwrap();
+        assert_eq!(fixes, ["converted Windows line endings"]);
+        assert_eq!(fs::read_to_string(&input).unwrap(), "1\n2\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("2024/06.meta.toml")).unwrap(),
+            "downloaded = \"1970-01-01T00:00:00Z\"\n\
+             sha256 = \"a6e2b7a040683432de03a18fd8a1939a2fdf82585b364bfc874bdd4095c4cae1\"\n\
+             length = 4\n"
+        );
+        assert_eq!(verify(&input).unwrap(), Check::Ok);
+
+        fs::write(&input, "1\n").unwrap();
+        assert_eq!(
+            verify(&input).unwrap(),
+            Check::Mismatch {
+                expected: 4,
+                actual: 2
+            }
+        );
+        fs::remove_file(meta_path(&input)).unwrap();
+        assert_eq!(verify(&input).unwrap(), Check::NoMeta);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn lock_unlock_round_trip() {
+        let dir = std::env::temp_dir().join(format!("advent-store-{}", std::process::id()));
+        let (input, store) = (dir.join("input"), dir.join("inputs.enc"));
+        let key = Key::weak("hunter2");
+        save(
+            &input.join("2024/06.txt"),
+            "....#.....\n",
+            OffsetDateTime::UNIX_EPOCH,
+        )
+        .unwrap();
+        save(
+            &input.join("alice/2024/06.txt"),
+            "..#\n",
+            OffsetDateTime::UNIX_EPOCH,
+        )
+        .unwrap();
+
+        let locked = lock(&input, &store, &key).unwrap();
+        assert_eq!(locked.len(), 4);
+        assert!(locked.iter().all(|(_, sync)| *sync == Sync::Written));
+        assert!(is_locked(&store, Path::new("alice/2024/06.txt")));
+        let encrypted = fs::read(store.join("2024/06.txt.enc")).unwrap();
+        assert!(!String::from_utf8_lossy(&encrypted).contains("....#....."));
+        assert!(
+            lock(&input, &store, &key)
+                .unwrap()
+                .iter()
+                .all(|(_, s)| *s == Sync::Unchanged)
+        );
+
+        fs::remove_dir_all(&input).unwrap();
+        let unlocked = unlock(&store, &input, &key).unwrap();
+        assert_eq!(unlocked.len(), 4);
+        assert_eq!(
+            fs::read_to_string(input.join("2024/06.txt")).unwrap(),
+            "....#.....\n"
+        );
+        assert_eq!(verify(&input.join("alice/2024/06.txt")).unwrap(), Check::Ok);
+
+        fs::write(input.join("2024/06.txt"), "edited\n").unwrap();
+        let sync = unlock_file(&store, &input, Path::new("2024/06.txt"), &key).unwrap();
+        assert_eq!(sync, Sync::Kept);
+        assert_eq!(
+            fs::read_to_string(input.join("2024/06.txt")).unwrap(),
+            "edited\n"
+        );
+
+        fs::remove_dir_all(&input).unwrap();
+        assert!(unlock(&store, &input, &Key::weak("hunter3")).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,240 @@
+//! Private leaderboards, from the JSON API of the site.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use yansi::Paint;
+
+use crate::calendar;
+
+/// How long a downloaded leaderboard is used before it is downloaded again,
+/// as the site asks not to poll more often.
+pub const CACHE_FOR: time::Duration = time::Duration::minutes(15);
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Missing for anonymous users.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// When the last star was earned, as a Unix timestamp.
+    pub last_star_ts: i64,
+    /// The stars earned, keyed by day and then part.
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Star {
+    /// When the star was earned, as a Unix timestamp.
+    pub get_star_ts: i64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Returns the number of seconds after the unlock it took to earn the star.
+    fn solved_in(&self, year: u32, day: u32, part: u32) -> Option<i64> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        let unlock = calendar::unlock(year, day).ok()?;
+        Some(star.get_star_ts - unlock.unix_timestamp())
+    }
+}
+
+pub fn parse(json: &str) -> Result<Leaderboard> {
+    serde_json::from_str(json)
+        .context("failed to parse the leaderboard, check that its id is right and you can see it")
+}
+
+impl Leaderboard {
+    /// Returns the members by local score, then by who got there first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|m| {
+            (
+                std::cmp::Reverse(m.local_score),
+                std::cmp::Reverse(m.stars),
+                m.last_star_ts,
+                m.id,
+            )
+        });
+        members
+    }
+
+    /// Returns the last day anyone has earned a star on.
+    pub fn last_day(&self) -> Option<u32> {
+        self.members
+            .values()
+            .flat_map(|m| m.completion_day_level.keys())
+            .max()
+            .copied()
+    }
+}
+
+/// Renders the ranking with the stars of each member per day.
+pub fn render_ranking(board: &Leaderboard, year: u32, color: bool) -> String {
+    let days = calendar::rules(year).map_or(25, |r| r.days);
+    let mut out = String::new();
+    let digits = |f: fn(u32) -> char| (1..=days).map(f).collect::<String>();
+    let tens = digits(|d| {
+        if d < 10 {
+            ' '
+        } else {
+            char::from(b'0' + (d / 10) as u8)
+        }
+    });
+    let ones = digits(|d| char::from(b'0' + (d % 10) as u8));
+    writeln!(out, "{:19}{tens}", "").unwrap();
+    writeln!(
+        out,
+        "{:<5}{:>6}{:>6}  {ones}  Name",
+        "Rank", "Score", "Stars"
+    )
+    .unwrap();
+
+    for (rank, member) in (1..).zip(board.ranked()) {
+        let grid: String = (1..=days)
+            .map(|day| {
+                let parts = member.completion_day_level.get(&day).map_or(0, |p| p.len());
+                match (parts, color) {
+                    (0, false) => ".".to_owned(),
+                    (1, false) => "+".to_owned(),
+                    (_, false) => "*".to_owned(),
+                    (0, true) => ".".dim().to_string(),
+                    (1, true) => "*".white().to_string(),
+                    (_, true) => "*".yellow().bold().to_string(),
+                }
+            })
+            .collect();
+        writeln!(
+            out,
+            "{:>3}) {:>6}{:>6}  {grid}  {}",
+            rank,
+            member.local_score,
+            member.stars,
+            member.display_name()
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Renders how long after the unlock each member solved both parts of the
+/// day, and the time between the parts.
+pub fn render_day(board: &Leaderboard, year: u32, day: u32) -> String {
+    let mut rows: Vec<_> = board
+        .members
+        .values()
+        .filter_map(|m| {
+            let part1 = m.solved_in(year, day, 1)?;
+            let part2 = m.solved_in(year, day, 2);
+            Some((m.display_name(), part1, part2))
+        })
+        .collect();
+    rows.sort_by_key(|&(_, part1, part2)| (part2.is_none(), part2, part1));
+
+    let heading = format!("Day {day}");
+    let width = rows
+        .iter()
+        .map(|r| r.0.chars().count())
+        .chain([heading.len()])
+        .max()
+        .unwrap();
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{heading:<width$}  {:>10}  {:>10}  {:>10}",
+        "Part 1", "Part 2", "Delta"
+    )
+    .unwrap();
+    for (name, part1, part2) in rows {
+        let (part2, delta) = match part2 {
+            Some(part2) => (duration(part2), duration(part2 - part1)),
+            None => ("-".to_owned(), "-".to_owned()),
+        };
+        writeln!(
+            out,
+            "{name:<width$}  {:>10}  {part2:>10}  {delta:>10}",
+            duration(part1)
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Formats seconds as `hh:mm:ss`, with as many hours as needed.
+fn duration(secs: i64) -> String {
+    let secs = secs.max(0);
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> Leaderboard {
+        parse(include_str!("../tests/fixtures/2024/leaderboard.json")).unwrap()
+    }
+
+    #[test]
+    fn parses() {
+        let board = board();
+        assert_eq!(board.members.len(), 4);
+        let names: Vec<_> = board.ranked().iter().map(|m| m.display_name()).collect();
+        assert_eq!(names, ["alice", "bob", "(anonymous user #3)", "carol"]);
+        assert_eq!(board.last_day(), Some(2));
+        assert!(parse("<html>").is_err());
+    }
+
+    #[test]
+    fn ranking() {
+        assert_eq!(
+            render_ranking(&board(), 2024, false),
+            "                            1111111111222222
+Rank  Score Stars  1234567890123456789012345  Name
+  1)     16     4  **.......................  alice
+  2)      9     3  *+.......................  bob
+  3)      2     1  +........................  (anonymous user #3)
+  4)      0     0  .........................  carol
+"
+        );
+    }
+
+    #[test]
+    fn day() {
+        assert_eq!(
+            render_day(&board(), 2024, 1),
+            "\
+Day 1                    Part 1      Part 2       Delta
+alice                  00:05:00    00:12:00    00:07:00
+bob                    00:07:00    00:25:00    00:18:00
+(anonymous user #3)    02:00:00           -           -
+"
+        );
+        assert_eq!(
+            render_day(&board(), 2024, 2),
+            "\
+Day 2      Part 1      Part 2       Delta
+alice    00:10:00    01:20:00    01:10:00
+bob      25:00:00           -           -
+"
+        );
+    }
+}
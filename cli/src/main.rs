@@ -1,29 +1,69 @@
+use std::collections::BTreeMap;
 use std::env;
+use std::ffi::OsString;
 use std::fmt::Display;
 use std::fs;
 use std::io;
-use std::io::IsTerminal;
+use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
 use std::process;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use argh::FromArgs;
-use serde::{Deserialize, Serialize};
-use yansi::Paint;
+use yansi::{Color, Paint};
+
+use crate::answers::Answers;
+use crate::submit::Verdict;
+
+mod answers;
+mod auth;
+mod bench;
+mod calendar;
+mod client;
+mod config;
+mod crypto;
+mod examples;
+mod html;
+mod inputs;
+mod leaderboard;
+mod puzzle;
+mod registry;
+mod runner;
+mod status;
+mod submit;
+mod summary;
+mod template;
+mod verify;
+mod watch;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
 
 /// 🎄 Festive Advent of Code solution management
 #[derive(Debug, FromArgs)]
-#[argh(example = "cargo advent -y 2021 -d 17 run")]
+#[argh(
+    example = "cargo advent -y 2021 -d 17 run",
+    example = "cargo advent -y all bench"
+)]
 struct Opt {
-    /// the puzzle year
+    /// the puzzle year, or "all" for every year
     #[argh(option, short = 'y')]
-    year: u32,
+    year: Option<Year>,
 
-    /// the puzzle day
+    /// the puzzle day or a list of days like "1,3,5-7", omit to run every
+    /// day of the year
     #[argh(option, short = 'd')]
-    day: u32,
+    day: Option<Days>,
 
-    /// the subcommand: bench, new, open, or a Cargo subcommand
+    /// the named profile whose session and inputs to use
+    #[argh(option, short = 'p')]
+    profile: Option<String>,
+
+    /// the subcommand: auth, bench, examples, fetch, inputs, leaderboard, new,
+    /// open, read, remove, status, submit, verify, wait, watch, or a Cargo subcommand
     #[argh(positional)]
     command: Command,
 
@@ -33,20 +73,90 @@ struct Opt {
 
 #[derive(Debug)]
 enum Command {
+    Auth,
     Bench,
+    Examples,
+    Fetch,
+    Inputs,
+    Leaderboard,
     New,
     Open,
+    Read,
+    Remove,
+    Status,
+    Submit,
+    Verify,
+    Wait,
+    Watch,
     Cargo(String),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Year {
+    All,
+    One(u32),
+}
+
+impl argh::FromArgValue for Year {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        match value {
+            "all" => Ok(Self::All),
+            _ => value
+                .parse()
+                .map(Self::One)
+                .map_err(|_| "expected a year or \"all\"".into()),
+        }
+    }
+}
+
+/// A selection of days, e.g. `1,3,5-7`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Days(Vec<u32>);
+
+impl argh::FromArgValue for Days {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        let mut days = Vec::new();
+        for item in value.split(',') {
+            let parse = |s: &str| {
+                s.trim()
+                    .parse::<u32>()
+                    .map_err(|_| format!("invalid day `{s}`"))
+            };
+            match item.split_once('-') {
+                Some((start, end)) => days.extend(parse(start)?..=parse(end)?),
+                None => days.push(parse(item)?),
+            }
+        }
+        days.sort();
+        days.dedup();
+        Ok(Self(days))
+    }
+}
+
 impl argh::FromArgValue for Command {
     fn from_arg_value(value: &str) -> Result<Self, String> {
         match value {
+            "examples" => Ok(Self::Examples),
+            "fetch" => Ok(Self::Fetch),
+            "inputs" => Ok(Self::Inputs),
+            "leaderboard" => Ok(Self::Leaderboard),
             "new" => Ok(Self::New),
             "open" => Ok(Self::Open),
+            "read" => Ok(Self::Read),
+            "remove" => Ok(Self::Remove),
+            "status" => Ok(Self::Status),
+            "auth" => Ok(Self::Auth),
             "bench" => Ok(Self::Bench),
+            "submit" => Ok(Self::Submit),
+            "verify" => Ok(Self::Verify),
+            "wait" => Ok(Self::Wait),
+            "watch" => Ok(Self::Watch),
             "check" | "build" | "test" | "run" | "clippy" => Ok(Self::Cargo(value.into())),
-            _ => Err("expected one of: bench, new, open, or a Cargo subcommand".into()),
+            _ => Err(
+                "expected one of: auth, bench, examples, fetch, inputs, leaderboard, new, open, read, \
+                 remove, status, submit, verify, wait, watch, or a Cargo subcommand"
+                    .into(),
+            ),
         }
     }
 }
@@ -55,100 +165,418 @@ fn main() -> Result<()> {
     let Opt {
         year,
         day,
+        profile,
         command,
         args,
-    } = argh::from_env();
+    } = parse_args(&[], &subcommand_args(env::args().skip(1).collect()));
+    let year = year.or(config::get().year.map(Year::One));
+    config::select_profile(profile);
 
-    match command {
-        Command::Bench => bench(year, day, &args),
-        Command::New => new(year, day),
-        Command::Open => open(year, day, &args),
-        Command::Cargo(cmd) => cargo(cmd, year, day, &args),
+    if let (Some(Year::One(year)), Some(Days(days))) = (year, &day) {
+        for &day in days {
+            calendar::validate(year, day)?;
+        }
+    }
+
+    let single = match (year, &day) {
+        (Some(Year::One(year)), Some(Days(days))) if days.len() == 1 => Some((year, days[0])),
+        _ => None,
+    };
+    let day = day.as_ref();
+
+    match (command, single) {
+        (Command::Auth, _) => auth(&args),
+        (Command::Verify, _) => verify(year, day, &args),
+        (Command::Bench, _) => bench(year, day, &args),
+        (Command::Fetch, _) => fetch(year, day),
+        (Command::Inputs, _) => inputs(&args),
+        (Command::Leaderboard, _) => leaderboard(year, day, &args),
+        (Command::Status, _) => progress(year, &args),
+        (Command::Examples, Some((year, day))) => examples(year, day, &args),
+        (Command::New, Some((year, day))) => new(year, day, &args),
+        (Command::Open, Some((year, day))) => open(year, day, &args),
+        (Command::Read, Some((year, day))) => read(year, day, &args),
+        (Command::Remove, Some((year, day))) => remove(year, day),
+        (Command::Submit, Some((year, day))) => submit(year, day, &args),
+        (Command::Wait, Some((year, day))) => wait(year, day),
+        (Command::Watch, Some((year, day))) => watch(year, day),
+        (Command::Cargo(cmd), Some((year, day))) => cargo(cmd, year, day, &args),
+        (Command::Cargo(cmd), None) => cargo_all(cmd, year, day, &args),
+        (_, None) => bail!("`-y/--year` and `-d/--day` are required for this subcommand"),
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
-struct Binary {
-    name: String,
-    path: PathBuf,
+/// Returns the year and day of every registered solution that matches the
+/// given year and day.
+fn days(year: Option<Year>, day: Option<&Days>) -> Result<Vec<(u32, u32)>> {
+    let mut days: Vec<_> = registered()?
+        .into_iter()
+        .filter(|&(y, _)| match year {
+            None | Some(Year::All) => true,
+            Some(Year::One(year)) => y == year,
+        })
+        .filter(|(_, d)| day.is_none_or(|Days(days)| days.contains(d)))
+        .collect();
+    days.sort();
+    if days.is_empty() {
+        bail!("no matching solutions in the registry");
+    }
+    Ok(days)
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-struct Binaries {
-    bin: Vec<Binary>,
+/// The library the solutions are registered in.
+fn registry_path() -> PathBuf {
+    workspace_dir().join("src/lib.rs")
 }
 
-fn download(url: &str) -> Result<String> {
-    let mut buf = Vec::new();
-    let mut easy = curl::easy::Easy::new();
-    easy.fail_on_error(true)?;
-    easy.follow_location(true)?;
-    easy.useragent("github.com/rossmacarthur/advent by ross@macarthur.io")?;
-    easy.cookie(&format!(
-        "session={}",
-        env::var("ADVENT_SESSION").context("`ADVENT_SESSION` must be set")?
-    ))?;
-    easy.url(url)?;
-    {
-        let mut transfer = easy.transfer();
-        transfer.write_function(|data| {
-            buf.extend_from_slice(data);
-            Ok(data.len())
-        })?;
-        transfer.perform()?;
+/// Returns the year and day of every registered solution.
+fn registered() -> Result<Vec<(u32, u32)>> {
+    let lib = fs::read_to_string(registry_path())?;
+    registry::days(&lib)
+}
+
+/// Removes `--name value` or `--name=value` from the arguments before `--`.
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>> {
+    let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    let prefix = format!("{name}=");
+    let Some(i) = args[..end]
+        .iter()
+        .position(|a| a == name || a.starts_with(&prefix))
+    else {
+        return Ok(None);
+    };
+    let arg = args.remove(i);
+    match arg.strip_prefix(&prefix) {
+        Some(value) => Ok(Some(value.into())),
+        None if i < end - 1 => Ok(Some(args.remove(i))),
+        None => bail!("`{name}` requires a value"),
+    }
+}
+
+/// Splits arguments into Cargo arguments and binary arguments at `--`.
+fn split_args(args: &[String]) -> (&[String], &[String]) {
+    match args.iter().position(|a| a == "--") {
+        Some(i) => (&args[..i], &args[i + 1..]),
+        None => (args, &[]),
+    }
+}
+
+/// Options for the `submit` subcommand.
+#[derive(Debug, FromArgs)]
+#[argh(example = "cargo advent -y 2024 -d 6 submit 1")]
+struct SubmitOpt {
+    /// the puzzle part, 1 or 2
+    #[argh(positional)]
+    part: u32,
+
+    /// the answer to submit, defaults to the output of the solution binary
+    #[argh(positional)]
+    answer: Option<String>,
+}
+
+/// Options for the `new` subcommand.
+#[derive(Debug, FromArgs)]
+#[argh(example = "cargo advent -y 2024 -d 6 new --template grid")]
+struct NewOpt {
+    /// the template to create the solution from: default, graph, grid, vm,
+    /// one from `templates` in the config, or a path to a `.rs` file
+    #[argh(option)]
+    template: Option<String>,
+}
+
+/// Options for the `leaderboard` subcommand.
+#[derive(Debug, FromArgs)]
+#[argh(example = "cargo advent -y 2024 leaderboard 123456")]
+struct LeaderboardOpt {
+    /// the id of the private leaderboard, the number at the end of its URL
+    #[argh(positional)]
+    id: u64,
+}
+
+/// Options for the `status` subcommand.
+#[derive(Debug, FromArgs)]
+#[argh(example = "cargo advent -y 2024 status --test")]
+struct StatusOpt {
+    /// also run the `default` test of every registered day, which can take
+    /// a while
+    #[argh(switch)]
+    test: bool,
+}
+
+/// Options for the `examples` subcommand.
+#[derive(Debug, FromArgs)]
+#[argh(example = "cargo advent -y 2024 -d 6 examples --pick 2")]
+struct ExamplesOpt {
+    /// the 1-based index of the code block on the puzzle page to use as the
+    /// example, when the automatic choice is wrong
+    #[argh(option)]
+    pick: Option<usize>,
+}
+
+/// Options for the `auth` subcommand.
+#[derive(Debug, FromArgs)]
+#[argh(example = "cargo advent auth check --all")]
+struct AuthOpt {
+    #[argh(subcommand)]
+    command: AuthCommand,
+}
+
+#[derive(Debug, FromArgs)]
+#[argh(subcommand)]
+enum AuthCommand {
+    Check(AuthCheckOpt),
+}
+
+/// Check that the session cookie is valid and show whose it is.
+#[derive(Debug, FromArgs)]
+#[argh(subcommand, name = "check")]
+struct AuthCheckOpt {
+    /// check every profile in the config
+    #[argh(switch)]
+    all: bool,
+}
+
+/// Options for the `inputs` subcommand.
+#[derive(Debug, FromArgs)]
+#[argh(example = "cargo advent inputs lock")]
+struct InputsOpt {
+    #[argh(subcommand)]
+    command: InputsCommand,
+}
+
+#[derive(Debug, FromArgs)]
+#[argh(subcommand)]
+enum InputsCommand {
+    Lock(LockOpt),
+    Unlock(UnlockOpt),
+}
+
+/// Encrypt the inputs into the committed store.
+#[derive(Debug, FromArgs)]
+#[argh(subcommand, name = "lock")]
+struct LockOpt {}
+
+/// Decrypt the inputs from the committed store.
+#[derive(Debug, FromArgs)]
+#[argh(subcommand, name = "unlock")]
+struct UnlockOpt {}
+
+/// Options for the `read` subcommand.
+#[derive(Debug, FromArgs)]
+#[argh(example = "cargo advent -y 2024 -d 6 read")]
+struct ReadOpt {
+    /// download the puzzle description even if it is cached
+    #[argh(switch)]
+    refresh: bool,
+}
+
+/// Options for the `verify` subcommand.
+#[derive(Debug, FromArgs)]
+#[argh(example = "cargo advent -y 2024 verify")]
+struct VerifyOpt {
+    /// store answers for parts missing from `answers.toml`
+    #[argh(switch)]
+    record: bool,
+}
+
+/// Inserts `--` after the subcommand so that any flags following it are
+/// passed through to the subcommand instead of being parsed as our own.
+fn subcommand_args(mut args: Vec<String>) -> Vec<String> {
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-y" | "--year" | "-d" | "--day" | "-p" | "--profile" => i += 2,
+            "--" => return args,
+            arg if arg.starts_with('-') => i += 1,
+            _ => {
+                args.insert(i + 1, "--".into());
+                return args;
+            }
+        }
     }
-    Ok(String::from_utf8(buf)?)
+    args
 }
 
-fn new(year: u32, day: u32) -> Result<()> {
-    let bin_name = format!("{year:04}{day:02}");
+/// Parses the arguments following a subcommand.
+fn parse_args<T: FromArgs>(command: &[&str], args: &[String]) -> T {
+    let name: Vec<_> = ["cargo advent"].iter().chain(command).copied().collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    T::from_args(&name, &args).unwrap_or_else(|early_exit| {
+        match early_exit.status {
+            Ok(()) => println!("{}", early_exit.output),
+            Err(()) => eprintln!("{}", early_exit.output),
+        }
+        process::exit(early_exit.status.map_or(1, |()| 0))
+    })
+}
+
+/// The workspace root, `CARGO_WORKSPACE_DIR` at runtime takes precedence over
+/// the one this was compiled in so that tests can point it elsewhere.
+fn workspace_dir() -> PathBuf {
+    env::var_os("CARGO_WORKSPACE_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(|| PathBuf::from(env!("CARGO_WORKSPACE_DIR")), PathBuf::from)
+}
+
+/// The site to talk to, overridable with `ADVENT_BASE_URL` or `base-url` in
+/// the config for testing.
+fn base_url() -> String {
+    env::var("ADVENT_BASE_URL")
+        .ok()
+        .or_else(|| config::get().base_url.clone())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.into())
+        .trim_end_matches('/')
+        .into()
+}
+
+/// The Cargo target directory, where caches and baselines are kept.
+fn target_dir() -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_dir().join("target"))
+}
+
+/// The directory puzzle inputs are stored in.
+fn input_root() -> PathBuf {
+    let input_dir = config::get().input_dir.as_deref();
+    workspace_dir().join(input_dir.unwrap_or("input".as_ref()))
+}
 
-    let workspace_dir = PathBuf::from(env!("CARGO_WORKSPACE_DIR"));
-    let manifest_path = workspace_dir.join("Cargo.toml");
+/// The directory inputs of the selected profile are stored in.
+fn input_dir() -> PathBuf {
+    match config::profile() {
+        Some(profile) => input_root().join(profile),
+        None => input_root(),
+    }
+}
+
+/// The committed store of encrypted inputs, mirroring the input directory.
+fn input_store() -> PathBuf {
+    workspace_dir().join("inputs.enc")
+}
+
+/// Returns the key of the encrypted input store, if one is configured.
+fn input_key() -> Result<Option<&'static crypto::Key>> {
+    static KEY: OnceLock<Option<crypto::Key>> = OnceLock::new();
+    if let Some(key) = KEY.get() {
+        return Ok(key.as_ref());
+    }
+    let key = match config::get().input_key()? {
+        Some(passphrase) => Some(inputs::key(&input_store(), &passphrase)?),
+        None => None,
+    };
+    Ok(KEY.get_or_init(|| key).as_ref())
+}
+
+/// The current time, overridable with an RFC 3339 timestamp in `ADVENT_NOW`
+/// for testing release times.
+fn now() -> Result<time::OffsetDateTime> {
+    static START: OnceLock<Instant> = OnceLock::new();
+    let start = *START.get_or_init(Instant::now);
+    match env::var("ADVENT_NOW") {
+        Ok(now) => {
+            let now = time::OffsetDateTime::parse(
+                &now,
+                &time::format_description::well_known::Rfc3339,
+            )
+            .with_context(|| format!("`ADVENT_NOW` is not an RFC 3339 timestamp: `{now}`"))?;
+            Ok(now + start.elapsed())
+        }
+        Err(_) => Ok(time::OffsetDateTime::now_utc()),
+    }
+}
+
+fn new(year: u32, day: u32, args: &[String]) -> Result<()> {
+    let NewOpt { template: chosen } = parse_args(&["new"], args);
+
+    let workspace_dir = workspace_dir();
     let bin = workspace_dir.join(format!("{year:04}/{day:02}.rs"));
 
     // Add {year}/{day}.rs file
-    const TEMPLATE: &str = include_str!("template.rs");
+    const PLACEHOLDER_EXAMPLES: &str = "        let input = parse_input(\"\").unwrap();
+        assert_eq!(part1(input.clone()), 1);
+        assert_eq!(part2(input), 2);
+";
     let bin_display = bin.strip_prefix(&workspace_dir).unwrap_or(&bin).display();
     if bin.exists() {
-        print("Checked", format!("binary source `{bin_display}`"));
+        print("Checked", format!("solution source `{bin_display}`"));
     } else {
-        let rendered = TEMPLATE
-            .replace("{ year }", &format!("{year:04}"))
-            .replace("{ day }", &format!("{day:02}"));
+        let name = chosen
+            .as_deref()
+            .or(config::get().template.as_deref())
+            .unwrap_or("default");
+        let template = template::load(name, &workspace_dir)?;
+        let url = format!("{}/{year}/day/{day}", base_url());
+        let page = match is_released(year, day)? {
+            true => client::get(&url)
+                .inspect_err(|err| warning("Skipped", format!("downloading the puzzle: {err:#}")))
+                .ok(),
+            false => None,
+        };
+        let saved = examples::load(&examples::dir(&workspace_dir, year, day))?;
+        let examples = match &page {
+            _ if !saved.is_empty() => saved,
+            Some(page) => extract_examples(year, day, page, None).unwrap_or_else(|err| {
+                warning("Skipped", format!("extracting examples: {err:#}"));
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+        let answer = |part: fn(&examples::Example) -> &Option<String>| {
+            examples
+                .iter()
+                .find_map(|e| part(e).as_deref())
+                .map(examples::literal)
+                .unwrap_or_default()
+        };
+        let vars = BTreeMap::from([
+            ("year", format!("{year:04}")),
+            ("day", format!("{day:02}")),
+            (
+                "title",
+                page.as_deref().and_then(puzzle::title).unwrap_or_default(),
+            ),
+            ("url", url),
+            (
+                "examples",
+                match examples.is_empty() {
+                    true => PLACEHOLDER_EXAMPLES.to_owned(),
+                    false => examples::render_test(year, day, &examples),
+                },
+            ),
+            (
+                "example",
+                examples
+                    .first()
+                    .map(|e| e.input.clone())
+                    .unwrap_or_default(),
+            ),
+            ("part1_answer", answer(|e| &e.part1)),
+            ("part2_answer", answer(|e| &e.part2)),
+        ]);
+        let rendered = template::render(&template, &vars)
+            .with_context(|| format!("failed to render template `{name}`"))?;
         fs::create_dir_all(bin.parent().unwrap())?;
         fs::write(&bin, rendered)?;
         print(
             "Created",
-            format!("binary source `{bin_display}` from template"),
+            format!("solution source `{bin_display}` from template `{name}`"),
         );
     }
 
-    // Update Cargo.toml
-    let manifest = fs::read_to_string(&manifest_path)?;
-    let index = manifest.find("[[bin]]").unwrap();
-    let (main, binaries) = manifest.split_at(index);
-    let mut bins: Binaries = toml::from_str(binaries)?;
-    let to_add = Binary {
-        name: bin_name.clone(),
-        path: bin.strip_prefix(&workspace_dir)?.to_owned(),
-    };
-    let added = !bins.bin.contains(&to_add);
-    bins.bin.push(to_add);
-    bins.bin.sort();
-    bins.bin.dedup();
-    let binaries = toml::to_string(&bins)?;
-    fs::write(&manifest_path, main.to_owned() + &binaries)?;
-
-    if added {
+    // Register the solution in src/lib.rs
+    let registry_path = registry_path();
+    let lib = fs::read_to_string(&registry_path)?;
+    if let Some(lib) = registry::add(&lib, year, day)? {
+        fs::write(&registry_path, lib)?;
         print(
             "Updated",
-            format!("Cargo manifest with `{bin_name}` binary"),
+            format!("solution registry with {year:04}/{day:02}"),
         );
     } else {
         print(
             "Checked",
-            format!("Cargo manifest contains `{bin_name}` binary"),
+            format!("solution registry contains {year:04}/{day:02}"),
         );
     }
 
@@ -157,112 +585,805 @@ fn new(year: u32, day: u32) -> Result<()> {
     Ok(())
 }
 
+/// Takes the day out of the solution registry, keeping its source.
+fn remove(year: u32, day: u32) -> Result<()> {
+    let registry_path = registry_path();
+    let lib = fs::read_to_string(&registry_path)?;
+    match registry::remove(&lib, year, day)? {
+        Some(lib) => {
+            fs::write(&registry_path, lib)?;
+            print(
+                "Updated",
+                format!("solution registry without {year:04}/{day:02}"),
+            );
+        }
+        None => warning(
+            "Skipped",
+            format!("solution registry has no {year:04}/{day:02}"),
+        ),
+    }
+    Ok(())
+}
+
 fn open(year: u32, day: u32, args: &[String]) -> Result<()> {
-    let url = format!("https://adventofcode.com/{year}/day/{day}");
-    let browser = args.first().map(|s| s.as_str()).unwrap_or("firefox");
-    open::with(url, browser)?;
+    let url = format!("{}/{year}/day/{day}", base_url());
+    let browser = args.first().or(config::get().browser.as_ref());
+    match browser {
+        Some(browser) => open::with(url, browser)?,
+        None => open::that(url)?,
+    }
+    Ok(())
+}
+
+/// Prints the puzzle description, downloading it unless cached.
+///
+/// The cache is refreshed until it contains both parts.
+fn read(year: u32, day: u32, args: &[String]) -> Result<()> {
+    let ReadOpt { refresh } = parse_args(&["read"], args);
+
+    let cached = fs::read_to_string(puzzle_path(year, day)).ok();
+    let markdown = match cached {
+        Some(markdown) if !refresh && puzzle::parts(&markdown) == 2 => markdown,
+        _ => download_puzzle(year, day)?,
+    };
+
+    println!();
+    print!("{}", puzzle::style(&markdown));
+    Ok(())
+}
+
+fn puzzle_path(year: u32, day: u32) -> PathBuf {
+    workspace_dir().join(format!("puzzles/{year:04}/{day:02}.md"))
+}
+
+/// Downloads the puzzle description and caches it as Markdown.
+fn download_puzzle(year: u32, day: u32) -> Result<String> {
+    let workspace_dir = workspace_dir();
+    let path = puzzle_path(year, day);
+    let path_display = path.strip_prefix(&workspace_dir).unwrap_or(&path).display();
+
+    let base_url = base_url();
+    let page = client::get(&format!("{base_url}/{year}/day/{day}"))?;
+    let markdown = puzzle::to_markdown(&page, &base_url)
+        .context("page does not contain a puzzle description")?;
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, &markdown)?;
+    print("Downloaded", format!("puzzle description `{path_display}`"));
+    Ok(markdown)
+}
+
+/// Counts down until the puzzle unlocks, then downloads its input and
+/// description, retrying while the input is not available yet.
+fn wait(year: u32, day: u32) -> Result<()> {
+    const RETRY_FOR: time::Duration = time::Duration::minutes(10);
+
+    let unlock = calendar::unlock(year, day)?;
+    let live = io::stdout().is_terminal();
+    let mut shown = false;
+    loop {
+        let remaining = unlock - now()?;
+        if !remaining.is_positive() {
+            break;
+        }
+        let message = format!(
+            "for {year:04}/{day:02}, unlocks at {} in {}",
+            calendar::format(unlock),
+            calendar::countdown(remaining)
+        );
+        if live {
+            print!("\r{:>12} {message}\x1b[K", "Waiting".bold().green());
+            io::stdout().flush()?;
+        } else if !shown {
+            print("Waiting", message);
+        }
+        shown = true;
+        // Wake up when the countdown ticks over to the next second.
+        let tick = remaining - time::Duration::seconds(remaining.whole_seconds());
+        thread::sleep(match tick.is_zero() {
+            true => Duration::from_secs(1),
+            false => tick.unsigned_abs(),
+        });
+    }
+    if live && shown {
+        println!();
+    }
+
+    loop {
+        match check_input(year, day) {
+            Ok(()) => break,
+            Err(err)
+                if err.downcast_ref::<client::NotUnlocked>().is_some()
+                    && now()? < unlock + RETRY_FOR =>
+            {
+                warning("Retrying", "the puzzle input is not available yet");
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    download_puzzle(year, day)?;
+    Ok(())
+}
+
+fn auth(args: &[String]) -> Result<()> {
+    let AuthOpt {
+        command: AuthCommand::Check(AuthCheckOpt { all }),
+    } = parse_args(&["auth"], args);
+
+    let url = format!("{}/settings", base_url());
+    if !all {
+        let user = auth::user(&client::get(&url)?).context(auth::LOGGED_OUT)?;
+        print("Logged in", format!("as `{user}`"));
+        return Ok(());
+    }
+
+    let profiles = &config::get().profiles;
+    if profiles.is_empty() {
+        bail!("no profiles in `advent.toml`");
+    }
+    let mut ok = true;
+    for name in profiles.keys() {
+        let user = config::get()
+            .profile_session(name)
+            .and_then(|session| client::get_as(&url, &session))
+            .and_then(|page| auth::user(&page).context(auth::LOGGED_OUT));
+        match user {
+            Ok(user) => print("Logged in", format!("profile `{name}` as `{user}`")),
+            Err(err) => {
+                error("Failed", format!("profile `{name}`: {err:#}"));
+                ok = false;
+            }
+        }
+    }
+    if !ok {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Downloads the examples from the puzzle page and stores them.
+fn examples(year: u32, day: u32, args: &[String]) -> Result<()> {
+    let ExamplesOpt { pick } = parse_args(&["examples"], args);
+    let examples = fetch_examples(year, day, pick)?;
+    if examples.is_empty() {
+        bail!("no code blocks found in the puzzle description");
+    }
+    println!();
+    println!("{}", examples::render_test(year, day, &examples));
     Ok(())
 }
 
+/// Downloads the puzzle page and extracts the examples from it.
+fn fetch_examples(year: u32, day: u32, pick: Option<usize>) -> Result<Vec<examples::Example>> {
+    let page = client::get(&format!("{}/{year}/day/{day}", base_url()))?;
+    extract_examples(year, day, &page, pick)
+}
+
+/// Extracts the examples from the puzzle page and writes them to
+/// `examples/{year}/{day}`.
+fn extract_examples(
+    year: u32,
+    day: u32,
+    page: &str,
+    pick: Option<usize>,
+) -> Result<Vec<examples::Example>> {
+    let extracted = examples::extract(page);
+    if extracted.is_ambiguous() && pick.is_none() {
+        warning(
+            "Ambiguous",
+            "several code blocks look like examples, use `--pick N` to choose another one",
+        );
+        for (n, candidate) in (1..).zip(&extracted.candidates) {
+            let first_line = candidate.text.lines().next().unwrap_or_default();
+            println!("{n:>12} part {}: {first_line}", candidate.part);
+        }
+    }
+    let examples = extracted.examples(pick)?;
+    if examples.is_empty() {
+        return Ok(examples);
+    }
+
+    let workspace_dir = workspace_dir();
+    let dir = examples::dir(&workspace_dir, year, day);
+    examples::save(&dir, &examples)?;
+    let dir_display = dir.strip_prefix(&workspace_dir).unwrap_or(&dir).display();
+    print(
+        "Extracted",
+        format!(
+            "{} example{} to `{dir_display}`",
+            examples.len(),
+            if examples.len() == 1 { "" } else { "s" }
+        ),
+    );
+    Ok(examples)
+}
+
+/// Returns whether the puzzle has been released.
+fn is_released(year: u32, day: u32) -> Result<bool> {
+    Ok(now()? >= calendar::unlock(year, day)?)
+}
+
 fn check_input(year: u32, day: u32) -> Result<()> {
     // Only try download if the puzzle has actually been released
-    let now = time::OffsetDateTime::now_utc();
-    let puzzle = time::PrimitiveDateTime::new(
-        time::Date::from_calendar_date(year as i32, time::Month::December, day as u8)?,
-        time::Time::from_hms(5, 0, 0)?,
-    )
-    .assume_utc();
-    if now < puzzle {
+    let unlock = calendar::unlock(year, day)?;
+    if now()? < unlock {
         warning(
             "Unavailable",
-            format!("puzzle input (year: {year:04}, day: {day:02})"),
+            format!(
+                "puzzle input (year: {year:04}, day: {day:02}) unlocks in {}",
+                calendar::countdown(unlock - now()?)
+            ),
         );
         return Ok(());
     }
 
     // Calculate input file path
-    let workspace_dir = PathBuf::from(env!("CARGO_WORKSPACE_DIR"));
-    let input = workspace_dir.join(format!("input/{year:04}/{day:02}.txt"));
-
-    // Create directory if not exists
-    fs::create_dir_all(input.parent().unwrap())?;
-
+    let workspace_dir = workspace_dir();
+    let input = input_dir().join(format!("{year:04}/{day:02}.txt"));
     let input_display = input
         .strip_prefix(&workspace_dir)
         .unwrap_or(&input)
         .display();
 
+    let rel = input.strip_prefix(input_root())?;
+    if !input.exists()
+        && inputs::is_locked(&input_store(), rel)
+        && let Some(key) = input_key()?
+    {
+        let store = input_store();
+        inputs::unlock_file(&store, &input_root(), rel, key)?;
+        let meta = inputs::meta_path(rel);
+        if inputs::is_locked(&store, &meta) {
+            inputs::unlock_file(&store, &input_root(), &meta, key)?;
+        }
+        print("Decrypted", format!("puzzle input `{input_display}`"));
+    }
+
     if !input.exists() {
-        let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-        let text = download(&url)?;
-        fs::write(&input, text)?;
+        let url = format!("{}/{year}/day/{day}/input", base_url());
+        let text = client::get(&url)?;
+        for fix in inputs::save(&input, &text, now()?)? {
+            warning(
+                "Normalized",
+                format!("puzzle input `{input_display}`: {fix}"),
+            );
+        }
         print("Downloaded", format!("puzzle input `{input_display}`"));
-    } else {
-        print("Verified", format!("puzzle input `{input_display}`"));
+        return Ok(());
     }
 
+    match inputs::verify(&input)? {
+        inputs::Check::Ok | inputs::Check::NoMeta => {
+            print("Verified", format!("puzzle input `{input_display}`"));
+            Ok(())
+        }
+        inputs::Check::Mismatch { expected, actual } => bail!(
+            "puzzle input `{input_display}` does not match its metadata, it has {actual} bytes \
+             instead of {expected} or was modified; delete it to download it again"
+        ),
+    }
+}
+
+/// Encrypts inputs into, or decrypts them from, the committed store.
+fn inputs(args: &[String]) -> Result<()> {
+    let InputsOpt { command } = parse_args(&["inputs"], args);
+    let key = input_key()?.context(
+        "no key for the encrypted inputs, set `ADVENT_INPUT_KEY`, or `input-key` or \
+         `input-key-command` in the user config",
+    )?;
+
+    let workspace_dir = workspace_dir();
+    let (input_root, store) = (input_root(), input_store());
+    let (synced, verb, dir) = match command {
+        InputsCommand::Lock(LockOpt {}) => {
+            (inputs::lock(&input_root, &store, key)?, "Locked", &store)
+        }
+        InputsCommand::Unlock(UnlockOpt {}) => (
+            inputs::unlock(&store, &input_root, key)?,
+            "Unlocked",
+            &input_root,
+        ),
+    };
+    let dir_display = dir.strip_prefix(&workspace_dir).unwrap_or(dir).display();
+
+    for (rel, sync) in &synced {
+        if *sync == inputs::Sync::Kept {
+            warning(
+                "Kept",
+                format!("`{}` which differs from the encrypted copy", rel.display()),
+            );
+        }
+    }
+    let written = synced
+        .iter()
+        .filter(|(_, s)| *s == inputs::Sync::Written)
+        .count();
+    print(
+        verb,
+        format!("{written} of {} files into `{dir_display}`", synced.len()),
+    );
     Ok(())
 }
 
-fn bench(year: u32, day: u32, args: &[String]) -> Result<()> {
-    check_input(year, day)?;
+/// A downloaded leaderboard, kept for [`leaderboard::CACHE_FOR`].
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct CachedLeaderboard {
+    /// When it was downloaded, in RFC 3339.
+    downloaded: String,
+    json: String,
+}
 
-    let bin_name = format!("{year:04}{day:02}");
+/// Shows a private leaderboard, and the solve times of a day on it.
+fn leaderboard(year: Option<Year>, day: Option<&Days>, args: &[String]) -> Result<()> {
+    let LeaderboardOpt { id } = parse_args(&["leaderboard"], args);
+    let year = match year {
+        Some(Year::One(year)) => year,
+        Some(Year::All) => bail!("leaderboards are per year, `-y all` is not supported"),
+        None => now()?.year() as u32,
+    };
 
-    let (cargo_args, bin_args) = match args.iter().position(|a| a == "--") {
-        Some(i) => (&args[..i], &args[i + 1..]),
-        None => (args, &[][..]),
+    let path = target_dir().join(format!("leaderboards/{year}-{id}.json"));
+    let cached: Option<CachedLeaderboard> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok());
+    let rfc3339 = &time::format_description::well_known::Rfc3339;
+    let age = cached
+        .as_ref()
+        .and_then(|c| time::OffsetDateTime::parse(&c.downloaded, rfc3339).ok())
+        .map(|downloaded| now().map(|now| now - downloaded))
+        .transpose()?;
+
+    let json = match (cached, age) {
+        (Some(cached), Some(age)) if age < leaderboard::CACHE_FOR => {
+            print(
+                "Cached",
+                format!(
+                    "leaderboard from {} minutes ago, refreshing after {}",
+                    age.whole_minutes(),
+                    calendar::countdown(leaderboard::CACHE_FOR - age)
+                ),
+            );
+            cached.json
+        }
+        (cached, _) => {
+            let url = format!("{}/{year}/leaderboard/private/view/{id}.json", base_url());
+            match client::get(&url) {
+                Ok(json) => {
+                    leaderboard::parse(&json)?;
+                    let downloaded = now()?.replace_nanosecond(0)?.format(rfc3339)?;
+                    let cached = CachedLeaderboard { downloaded, json };
+                    fs::create_dir_all(path.parent().unwrap())?;
+                    fs::write(&path, serde_json::to_string(&cached)?)?;
+                    print("Downloaded", format!("leaderboard `{id}` of {year}"));
+                    cached.json
+                }
+                Err(err) => match cached {
+                    Some(cached) => {
+                        warning("Outdated", format!("leaderboard shown, {err:#}"));
+                        cached.json
+                    }
+                    None => return Err(err),
+                },
+            }
+        }
     };
 
-    let status = process::Command::new(env!("CARGO"))
-        .args([
-            "run",
-            "--features",
-            "default-inputs",
-            "--release",
-            "--bin",
-            &bin_name,
-        ])
-        .args(cargo_args)
-        .args(["--", "--bench"])
-        .args(bin_args)
-        .status()?;
+    let board = leaderboard::parse(&json)?;
+    let day = match day {
+        Some(Days(days)) if days.len() == 1 => Some(days[0]),
+        Some(_) => bail!("`-d/--day` takes a single day for this subcommand"),
+        None => board.last_day(),
+    };
+    println!();
+    print!(
+        "{}",
+        leaderboard::render_ranking(&board, year, io::stdout().is_terminal())
+    );
+    if let Some(day) = day {
+        println!();
+        print!("{}", leaderboard::render_day(&board, year, day));
+    }
+    Ok(())
+}
 
-    process::exit(status.code().unwrap())
+/// Rebuilds and reruns the day whenever its solution, input or examples
+/// change.
+fn watch(year: u32, day: u32) -> Result<()> {
+    let workspace_dir = workspace_dir();
+    let paths = [
+        workspace_dir.join(format!("{year:04}/{day:02}.rs")),
+        input_dir().join(format!("{year:04}/{day:02}.txt")),
+        examples::dir(&workspace_dir, year, day),
+    ];
+    watch::watch(year, day, &workspace_dir, &paths)
 }
 
-fn cargo(cmd: String, year: u32, day: u32, args: &[String]) -> Result<()> {
-    check_input(year, day)?;
+/// Shows the stars and local progress of every released day of the years.
+fn progress(year: Option<Year>, args: &[String]) -> Result<()> {
+    let StatusOpt { test } = parse_args(&["status"], args);
+    let this_year = now()?.year() as u32;
+    let years = match year {
+        Some(Year::One(year)) => vec![year],
+        Some(Year::All) => (calendar::FIRST_YEAR..=this_year).collect(),
+        None => vec![this_year],
+    };
+
+    let workspace_dir = workspace_dir();
+    let registered = registered()?;
+    let answers = Answers::load(&answers::path(&workspace_dir, config::profile()))?;
+    let color = io::stdout().is_terminal();
+
+    for year in years {
+        let released: Vec<u32> = (1..=calendar::rules(year)?.days)
+            .map(|day| is_released(year, day).map(|r| (day, r)))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .filter_map(|(day, released)| released.then_some(day))
+            .collect();
+        if released.is_empty() {
+            print(
+                "Unavailable",
+                format!(
+                    "{year} starts in {}",
+                    calendar::countdown(calendar::unlock(year, 1)? - now()?)
+                ),
+            );
+            continue;
+        }
+
+        let stars = match client::get(&format!("{}/{year}", base_url())) {
+            Ok(page) => Some(status::stars(&page)),
+            Err(err) => {
+                warning("Skipped", format!("stars of {year}: {err:#}"));
+                None
+            }
+        };
+
+        let mut days = Vec::new();
+        for day in released {
+            let registered = registered.contains(&(year, day));
+            let input = input_dir().join(format!("{year:04}/{day:02}.txt"));
+            let input = if input.exists() {
+                status::Input::Present
+            } else if inputs::is_locked(&input_store(), input.strip_prefix(input_root())?) {
+                status::Input::Locked
+            } else {
+                status::Input::Missing
+            };
+            let test = match test && registered {
+                true => Some(check_input(year, day).is_ok() && runner::test_default(year, day)?),
+                false => None,
+            };
+            let parts = calendar::parts(year, day)?;
+            let answers = (1..=parts)
+                .filter(|&part| {
+                    answers
+                        .get(year, day, part)
+                        .is_some_and(|p| p.answer.is_some())
+                })
+                .count() as u32;
+            days.push(status::Day {
+                day,
+                stars: stars.as_ref().map(|s| s.get(&day).copied().unwrap_or(0)),
+                registered,
+                input,
+                test,
+                answers,
+                parts,
+            });
+        }
+
+        println!();
+        println!("{year}");
+        print!("{}", status::render(&days, color));
+        if let Some(stars) = &stars {
+            print(
+                "Stars",
+                format!(
+                    "{} of {} in {year}",
+                    stars.values().sum::<u32>(),
+                    2 * calendar::rules(year)?.days
+                ),
+            );
+        }
+        let unfinished: Vec<_> = days
+            .iter()
+            .filter(|d| !d.is_finished())
+            .map(|d| d.day.to_string())
+            .collect();
+        if !unfinished.is_empty() {
+            warning(
+                "Unfinished",
+                format!("{year} days {}", unfinished.join(", ")),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Downloads every released input of the selected years that is missing and
+/// verifies the ones already present.
+fn fetch(year: Option<Year>, day: Option<&Days>) -> Result<()> {
+    let years = match year.context("`-y/--year` is required")? {
+        Year::One(year) => vec![year],
+        Year::All => (calendar::FIRST_YEAR..=now()?.year() as u32).collect(),
+    };
+    let mut failed = 0;
+    for year in years {
+        let days = calendar::rules(year)?.days;
+        for day in (1..=days).filter(|d| day.is_none_or(|Days(days)| days.contains(d))) {
+            if !is_released(year, day)? {
+                break;
+            }
+            if let Err(err) = check_input(year, day) {
+                error("Failed", format!("{year:04}/{day:02}: {err:#}"));
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        bail!("{failed} inputs could not be fetched or verified");
+    }
+    Ok(())
+}
+
+fn submit(year: u32, day: u32, args: &[String]) -> Result<()> {
+    let SubmitOpt { part, answer } = parse_args(&["submit"], args);
+    if !(1..=2).contains(&part) {
+        bail!("part must be 1 or 2");
+    }
 
-    let bin_name = format!("{year:04}{day:02}");
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            check_input(year, day)?;
+            let output = runner::run(year, day, &[])?;
+            runner::parse_answers(&output)
+                .remove(&part)
+                .with_context(|| format!("no answer for part {part} in output"))?
+        }
+    };
+
+    let answers_path = answers::path(&workspace_dir(), config::profile());
+    let mut answers = Answers::load(&answers_path)?;
+    let known = answers.entry(year, day, part);
+    if known.answer.as_ref() == Some(&answer) {
+        print("Correct", format!("`{answer}` was already accepted"));
+        return Ok(());
+    }
+    if let Some(reason) = known.known_wrong(&answer) {
+        warning(
+            "Skipped",
+            format!("`{answer}` is known to be wrong ({reason})"),
+        );
+        return Ok(());
+    }
+
+    let url = format!("{}/{year}/day/{day}/answer", base_url());
+    let form = format!("level={part}&answer={}", url_encode(&answer));
+    let verdict = submit::parse_verdict(&client::post(&url, &form)?);
+    match &verdict {
+        Verdict::Correct => {
+            known.answer = Some(answer.clone());
+            print("Correct", format!("`{answer}` for part {part}"));
+        }
+        Verdict::Wrong(reason) => {
+            known.rejected.push(answers::Rejected {
+                answer: answer.clone(),
+                reason: *reason,
+            });
+            warning("Wrong", format!("`{answer}` is {reason}"));
+        }
+        verdict => warning("Unsubmitted", format!("`{answer}`: {verdict}")),
+    }
+    answers.save(&answers_path)?;
+
+    Ok(())
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{b:02X}"),
+        })
+        .collect()
+}
 
-    let status = process::Command::new(env!("CARGO"))
-        .args([
-            &cmd,
-            "--features",
-            "default-inputs",
-            "--release",
-            "--bin",
-            &bin_name,
-        ])
-        .args(args)
-        .status()?;
+fn verify(year: Option<Year>, day: Option<&Days>, args: &[String]) -> Result<()> {
+    let VerifyOpt { record } = parse_args(&["verify"], args);
+
+    if !verify::verify(&days(year, day)?, record)? {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Benchmarks the selected days.
+///
+/// Without any options a single day runs the binary's own benchmark output and
+/// several days print a combined summary. Otherwise the per-stage timings are
+/// collected over `--samples` runs and printed as text, JSON or CSV, saved as
+/// a baseline or compared with one.
+fn bench(year: Option<Year>, day: Option<&Days>, args: &[String]) -> Result<()> {
+    let mut args = args.to_vec();
+    let format: Option<bench::Format> = take_option(&mut args, "--format")?
+        .map(|f| f.parse())
+        .transpose()?
+        .or(config::get().format);
+    let samples: usize = take_option(&mut args, "--samples")?
+        .map(|s| s.parse())
+        .transpose()?
+        .unwrap_or(5);
+    let save_baseline = take_option(&mut args, "--save-baseline")?;
+    let baseline = take_option(&mut args, "--baseline")?;
+    let threshold: f64 = take_option(&mut args, "--threshold")?
+        .map(|t| t.parse())
+        .transpose()?
+        .unwrap_or(5.0);
+    let (cargo_args, bin_args) = split_args(&args);
+
+    let days = days(Some(year.context("`-y/--year` is required")?), day)?;
+
+    if format.is_none() && save_baseline.is_none() && baseline.is_none() {
+        if let [(year, day)] = days[..] {
+            check_input(year, day)?;
+            let status = runner::cargo("run", Some(runner::day_input(year, day).as_os_str()))
+                .args(cargo_args)
+                .arg("--")
+                .args(runner::day_args(year, day))
+                .arg("--bench")
+                .args(bin_args)
+                .status()?;
+            process::exit(status.code().unwrap())
+        }
+
+        let bin_args: Vec<_> = ["--bench"]
+            .into_iter()
+            .chain(bin_args.iter().map(String::as_str))
+            .collect();
+        if !summary::run_all(&days, &bin_args)? {
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Load the baseline before spending time on measurements
+    let baseline = baseline
+        .map(|name| bench::load_baseline(&name))
+        .transpose()?;
+
+    let format = format.unwrap_or(bench::Format::Text);
+    if format != bench::Format::Text && baseline.is_none() {
+        STDOUT_RESERVED.store(true, Ordering::Relaxed);
+    }
+    let bin_args: Vec<_> = bin_args.iter().map(String::as_str).collect();
+    let mut results = Vec::new();
+    for &(year, day) in &days {
+        check_input(year, day)?;
+    }
+    for (year, day) in days {
+        print("Benchmarking", format!("{year:04}/{day:02}"));
+        results.push(bench::measure(year, day, samples, &bin_args)?);
+    }
+
+    if let Some(name) = save_baseline {
+        let path = bench::save_baseline(&name, &results)?;
+        print(
+            "Saved",
+            format!("baseline `{name}` to `{}`", path.display()),
+        );
+    }
+
+    let Some(baseline) = baseline else {
+        io::stdout().write_all(bench::render(&results, format)?.as_bytes())?;
+        return Ok(());
+    };
+    let changes = bench::compare(&baseline, &results, threshold);
+    print!("{}", bench::render_comparison(&changes));
+    let regressed: Vec<_> = changes
+        .iter()
+        .filter(|c| c.regressed)
+        .map(|c| format!("{:04}/{:02} {}", c.year, c.day, c.stage))
+        .collect();
+    if !regressed.is_empty() {
+        error(
+            "Regressed",
+            format!("over {threshold}%: {}", regressed.join(", ")),
+        );
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Runs a Cargo subcommand for every selected day.
+///
+/// `run` and `test` go day by day and print a combined summary, any other
+/// subcommand is invoked once for the whole solutions library.
+fn cargo_all(cmd: String, year: Option<Year>, day: Option<&Days>, args: &[String]) -> Result<()> {
+    let days = days(Some(year.context("`-y/--year` is required")?), day)?;
+
+    let ok = match cmd.as_str() {
+        "run" => {
+            if take_option(&mut args.to_vec(), "--input")?.is_some() {
+                bail!("`--input` runs a single day, use `-d/--day` to choose it");
+            }
+            let (_, bin_args) = split_args(args);
+            let bin_args: Vec<_> = bin_args.iter().map(String::as_str).collect();
+            summary::run_all(&days, &bin_args)?
+        }
+        "test" => summary::test_all(&days, args)?,
+        // The solutions are a single library, checked or built as a whole.
+        _ => runner::cargo(&cmd, None).args(args).status()?.success(),
+    };
+    if !ok {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn cargo(cmd: String, year: u32, day: u32, args: &[String]) -> Result<()> {
+    let mut args = args.to_vec();
+    let input = match cmd.as_str() {
+        "run" => take_option(&mut args, "--input")?,
+        _ => None,
+    };
+    let input = match input {
+        Some(input) if input == "-" => OsString::from(input),
+        Some(path) => {
+            let path = std::path::absolute(&path)?;
+            if !path.is_file() {
+                bail!("input file `{}` does not exist", path.display());
+            }
+            path.into()
+        }
+        None => {
+            check_input(year, day)?;
+            runner::day_input(year, day).into()
+        }
+    };
+
+    let (cargo_args, bin_args) = split_args(&args);
+    let mut command = runner::cargo(&cmd, Some(&input));
+    command.args(cargo_args);
+    match cmd.as_str() {
+        "run" => command.arg("--").args(runner::day_args(year, day)),
+        "test" => command.args(["--", &runner::test_filter(year, day)]),
+        _ if !bin_args.is_empty() => command.arg("--"),
+        _ => &mut command,
+    };
+    let status = command.args(bin_args).status()?;
 
     process::exit(status.code().unwrap())
 }
 
-fn print(header: &str, message: impl Display) {
-    if io::stdout().is_terminal() {
-        println!("{:>12} {}", Paint::new(&header).bold().green(), message);
+/// Set when stdout carries machine-readable output, status messages then go
+/// to stderr instead.
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+
+fn status(header: &str, message: impl Display, color: Color) {
+    if STDOUT_RESERVED.load(Ordering::Relaxed) {
+        if io::stderr().is_terminal() {
+            eprintln!("{:>12} {}", Paint::new(&header).bold().fg(color), message);
+        } else {
+            eprintln!("{:>12} {}", header, message);
+        }
+    } else if io::stdout().is_terminal() {
+        println!("{:>12} {}", Paint::new(&header).bold().fg(color), message);
     } else {
         println!("{:>12} {}", header, message);
     }
 }
 
+fn print(header: &str, message: impl Display) {
+    status(header, message, Color::Green);
+}
+
+fn error(header: &str, message: impl Display) {
+    status(header, message, Color::Red);
+}
+
 fn warning(header: &str, message: impl Display) {
-    if io::stdout().is_terminal() {
-        println!("{:>12} {}", Paint::new(&header).bold().yellow(), message);
-    } else {
-        println!("{:>12} {}", header, message);
-    }
+    status(header, message, Color::Yellow);
 }
@@ -0,0 +1,240 @@
+use std::io;
+use std::io::IsTerminal;
+
+use yansi::Paint;
+
+use crate::html::{self, Element, Node};
+
+/// Converts the puzzle descriptions on a day's page to Markdown.
+///
+/// Returns `None` if the page has no puzzle description, e.g. because the
+/// puzzle has not been released yet.
+pub fn to_markdown(page: &str, base_url: &str) -> Option<String> {
+    let nodes = html::parse(page);
+    let articles = html::find_all(&nodes, &|e| e.name == "article" && e.has_class("day-desc"));
+    if articles.is_empty() {
+        return None;
+    }
+    let mut out = String::new();
+    for article in articles {
+        blocks(article, base_url, &mut out);
+    }
+    Some(out.trim_end().to_owned() + "\n")
+}
+
+/// Returns the title of the puzzle, e.g. `Day 6: Guard Gallivant`.
+pub fn title(page: &str) -> Option<String> {
+    let nodes = html::parse(page);
+    let article = html::find_all(&nodes, &|e| e.name == "article" && e.has_class("day-desc"))
+        .into_iter()
+        .next()?;
+    let heading = article.find_all(&|e| e.name == "h2").into_iter().next()?;
+    let title = heading.text();
+    let title = title.trim().trim_matches('-').trim();
+    (!title.is_empty()).then(|| title.to_owned())
+}
+
+/// Returns the number of puzzle parts in the given Markdown.
+pub fn parts(markdown: &str) -> usize {
+    markdown.lines().filter(|l| l.starts_with("## ")).count()
+}
+
+fn blocks(e: &Element, base_url: &str, out: &mut String) {
+    for child in &e.children {
+        match child {
+            Node::Text(text) if text.trim().is_empty() => {}
+            Node::Text(text) => {
+                out.push_str(&collapse(text));
+                out.push_str("\n\n");
+            }
+            Node::Element(e) => match e.name.as_str() {
+                "h2" => {
+                    out.push_str("## ");
+                    out.push_str(&inline(&e.children, base_url));
+                    out.push_str("\n\n");
+                }
+                "p" => {
+                    out.push_str(&inline(&e.children, base_url));
+                    out.push_str("\n\n");
+                }
+                "pre" => {
+                    out.push_str("```\n");
+                    out.push_str(e.text().trim_end_matches('\n'));
+                    out.push_str("\n```\n\n");
+                }
+                "ul" | "ol" => {
+                    list(e, base_url, 0, out);
+                    out.push('\n');
+                }
+                _ => blocks(e, base_url, out),
+            },
+        }
+    }
+}
+
+fn list(e: &Element, base_url: &str, depth: usize, out: &mut String) {
+    let items = e.children.iter().filter_map(|n| match n {
+        Node::Element(li) if li.name == "li" => Some(li),
+        _ => None,
+    });
+    for (i, li) in items.enumerate() {
+        let (nested, text): (Vec<_>, Vec<_>) = li
+            .children
+            .iter()
+            .cloned()
+            .partition(|n| matches!(n, Node::Element(e) if e.name == "ul" || e.name == "ol"));
+        let marker = match e.name.as_str() {
+            "ol" => format!("{}.", i + 1),
+            _ => "-".into(),
+        };
+        out.push_str(&format!(
+            "{:indent$}{marker} {}\n",
+            "",
+            inline(&text, base_url),
+            indent = depth * 2
+        ));
+        for n in &nested {
+            if let Node::Element(nested) = n {
+                list(nested, base_url, depth + 1, out);
+            }
+        }
+    }
+}
+
+fn inline(nodes: &[Node], base_url: &str) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(e) => match e.name.as_str() {
+                "em" => out.push_str(&format!("*{}*", inline(&e.children, base_url).trim())),
+                "code" => {
+                    let code = format!("`{}`", e.text());
+                    if e.find_all(&|e| e.name == "em").is_empty() {
+                        out.push_str(&code);
+                    } else {
+                        out.push_str(&format!("**{code}**"));
+                    }
+                }
+                "a" => {
+                    let href = e.attr("href").unwrap_or_default();
+                    let href = match href.starts_with('/') {
+                        true => format!("{base_url}{href}"),
+                        false => href.to_owned(),
+                    };
+                    out.push_str(&format!("[{}]({href})", inline(&e.children, base_url)));
+                }
+                "br" => out.push('\n'),
+                _ => out.push_str(&inline(&e.children, base_url)),
+            },
+        }
+    }
+    collapse(&out)
+}
+
+/// Collapses runs of whitespace into a single space, keeping line breaks.
+fn collapse(s: &str) -> String {
+    s.lines()
+        .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Styles Markdown for display on a terminal.
+///
+/// Only the subset produced by [`to_markdown`] is understood. When stdout is
+/// not a terminal the Markdown is returned unchanged.
+pub fn style(markdown: &str) -> String {
+    if !io::stdout().is_terminal() {
+        return markdown.to_owned();
+    }
+    let mut out = String::new();
+    let mut in_code = false;
+    for line in markdown.lines() {
+        if line == "```" {
+            in_code = !in_code;
+            continue;
+        }
+        let styled = if in_code {
+            format!("    {}", line.cyan())
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            heading.bold().green().to_string()
+        } else {
+            style_inline(line)
+        };
+        out.push_str(&styled);
+        out.push('\n');
+    }
+    out
+}
+
+fn style_inline(line: &str) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+    while !rest.is_empty() {
+        if let Some((code, after)) = delimited(rest, "**`", "`**") {
+            out.push_str(&code.bold().bright_white().to_string());
+            rest = after;
+        } else if let Some((code, after)) = delimited(rest, "`", "`") {
+            out.push_str(&code.cyan().to_string());
+            rest = after;
+        } else if let Some((em, after)) = delimited(rest, "*", "*") {
+            out.push_str(&em.bold().bright_white().to_string());
+            rest = after;
+        } else if let Some((link, after)) = link(rest) {
+            out.push_str(&link.underline().to_string());
+            rest = after;
+        } else {
+            let c = rest.chars().next().unwrap();
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    out
+}
+
+fn delimited<'a>(s: &'a str, open: &str, close: &str) -> Option<(&'a str, &'a str)> {
+    let inner = s.strip_prefix(open)?;
+    let end = inner.find(close).filter(|&i| i > 0)?;
+    Some((&inner[..end], &inner[end + close.len()..]))
+}
+
+fn link(s: &str) -> Option<(&str, &str)> {
+    let (text, rest) = delimited(s, "[", "](")?;
+    let end = rest.find(')')?;
+    Some((text, &rest[end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_URL: &str = "https://adventofcode.com";
+
+    #[test]
+    fn part_one() {
+        let page = include_str!("../tests/fixtures/2024/06-part1.html");
+        let markdown = to_markdown(page, BASE_URL).unwrap();
+        assert_eq!(markdown, include_str!("../tests/fixtures/2024/06-part1.md"));
+        assert_eq!(parts(&markdown), 1);
+        assert_eq!(title(page).as_deref(), Some("Day 6: Guard Gallivant"));
+    }
+
+    #[test]
+    fn both_parts() {
+        let page = include_str!("../tests/fixtures/2024/06-part2.html");
+        let markdown = to_markdown(page, BASE_URL).unwrap();
+        assert_eq!(markdown, include_str!("../tests/fixtures/2024/06-part2.md"));
+        assert_eq!(parts(&markdown), 2);
+    }
+
+    #[test]
+    fn not_released() {
+        assert_eq!(
+            to_markdown("<html><body>404 Not Found</body></html>", BASE_URL),
+            None
+        );
+        assert_eq!(title("<html><body>404 Not Found</body></html>"), None);
+    }
+}
@@ -0,0 +1,219 @@
+//! Editing the registry of solutions in `src/lib.rs`.
+//!
+//! The `solutions!` invocation lists one day per line, grouped by year:
+//!
+//! ```text
+//! solutions! {
+//!     y2024 = "../2024" {
+//!         d01 = "01.rs",
+//!     }
+//! }
+//! ```
+//!
+//! Days are added and removed line by line, so that the rest of the file is
+//! kept exactly as it was.
+
+use anyhow::{Context, Result, bail};
+
+/// The lines of the `solutions!` invocation.
+#[derive(Debug)]
+struct Registry {
+    /// The line closing the invocation.
+    end: usize,
+    years: Vec<Year>,
+}
+
+#[derive(Debug)]
+struct Year {
+    year: u32,
+    header: usize,
+    /// The line closing the year.
+    end: usize,
+    /// The days with their lines.
+    days: Vec<(u32, usize)>,
+}
+
+/// Returns the year and day of every registered solution.
+pub fn days(lib: &str) -> Result<Vec<(u32, u32)>> {
+    let lines: Vec<&str> = lib.split_inclusive('\n').collect();
+    let registry = parse(&lines)?;
+    Ok(registry
+        .years
+        .iter()
+        .flat_map(|y| y.days.iter().map(|&(day, _)| (y.year, day)))
+        .collect())
+}
+
+/// Registers the solution of a day, keeping years and days sorted.
+///
+/// Returns `None` if it is already registered.
+pub fn add(lib: &str, year: u32, day: u32) -> Result<Option<String>> {
+    let lines: Vec<&str> = lib.split_inclusive('\n').collect();
+    let registry = parse(&lines)?;
+    let nl = newline(lib);
+    let day_line = format!("        d{day:02} = \"{day:02}.rs\",{nl}");
+
+    let (at, insert) = match registry.years.iter().find(|y| y.year == year) {
+        Some(y) if y.days.iter().any(|&(d, _)| d == day) => return Ok(None),
+        Some(y) => {
+            let next = y.days.iter().find(|&&(d, _)| d > day);
+            (next.map_or(y.end, |&(_, line)| line), day_line)
+        }
+        None => {
+            let next = registry.years.iter().find(|y| y.year > year);
+            let block = format!("    y{year} = \"../{year}\" {{{nl}{day_line}    }}{nl}");
+            (next.map_or(registry.end, |y| y.header), block)
+        }
+    };
+    Ok(Some(lines[..at].concat() + &insert + &lines[at..].concat()))
+}
+
+/// Takes the solution of a day out of the registry, and its year if it was
+/// the last day of it.
+///
+/// Returns `None` if it is not registered.
+pub fn remove(lib: &str, year: u32, day: u32) -> Result<Option<String>> {
+    let lines: Vec<&str> = lib.split_inclusive('\n').collect();
+    let registry = parse(&lines)?;
+    let Some(y) = registry.years.iter().find(|y| y.year == year) else {
+        return Ok(None);
+    };
+    let Some(&(_, line)) = y.days.iter().find(|&&(d, _)| d == day) else {
+        return Ok(None);
+    };
+    let removed = match y.days.len() {
+        1 => y.header..y.end + 1,
+        _ => line..line + 1,
+    };
+    Ok(Some(
+        lines[..removed.start].concat() + &lines[removed.end..].concat(),
+    ))
+}
+
+fn newline(lib: &str) -> &'static str {
+    match lib.contains("\r\n") {
+        true => "\r\n",
+        false => "\n",
+    }
+}
+
+fn parse(lines: &[&str]) -> Result<Registry> {
+    let start = lines
+        .iter()
+        .position(|l| l.trim_end() == "solutions! {")
+        .context("no `solutions!` registry in `src/lib.rs`")?;
+
+    let mut years: Vec<Year> = Vec::new();
+    let mut in_year = false;
+    for (i, line) in lines.iter().enumerate().skip(start + 1) {
+        let line = line.trim();
+        let unexpected = || {
+            format!(
+                "unexpected `{line}` on line {} of the `solutions!` registry in `src/lib.rs`",
+                i + 1
+            )
+        };
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        if line == "}" {
+            match in_year {
+                true => {
+                    years.last_mut().unwrap().end = i;
+                    in_year = false;
+                    continue;
+                }
+                false => return Ok(Registry { end: i, years }),
+            }
+        }
+        match (in_year, line.split_once(" = ")) {
+            (false, Some((name, _))) if line.ends_with('{') => {
+                let year = name.strip_prefix('y').and_then(|y| y.parse().ok());
+                years.push(Year {
+                    year: year.with_context(unexpected)?,
+                    header: i,
+                    end: i,
+                    days: Vec::new(),
+                });
+                in_year = true;
+            }
+            (true, Some((name, _))) if line.ends_with(',') => {
+                let day = name.strip_prefix('d').and_then(|d| d.parse().ok());
+                let day = day.with_context(unexpected)?;
+                years.last_mut().unwrap().days.push((day, i));
+            }
+            _ => bail!(unexpected()),
+        }
+    }
+    bail!("the `solutions!` registry in `src/lib.rs` is not closed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = include_str!("../tests/fixtures/registry/lib.rs");
+
+    #[test]
+    fn lists_days() {
+        assert_eq!(days(LIB).unwrap(), [(2024, 1), (2024, 3), (2025, 1)]);
+        assert!(days("fn main() {}\n").is_err());
+        let err = days("solutions! {\n    y2024 {\n}\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unexpected `y2024 {` on line 2 of the `solutions!` registry in `src/lib.rs`"
+        );
+    }
+
+    #[test]
+    fn add_day() {
+        assert_eq!(
+            add(LIB, 2024, 2).unwrap().unwrap(),
+            include_str!("../tests/fixtures/registry/add-day.rs")
+        );
+        assert_eq!(add(LIB, 2024, 3).unwrap(), None);
+    }
+
+    #[test]
+    fn add_year() {
+        let added = add(LIB, 2023, 5).unwrap().unwrap();
+        assert_eq!(
+            added,
+            include_str!("../tests/fixtures/registry/add-year.rs")
+        );
+        assert_eq!(days(&added).unwrap()[0], (2023, 5));
+
+        let added = add(LIB, 2026, 1).unwrap().unwrap();
+        assert_eq!(days(&added).unwrap().last(), Some(&(2026, 1)));
+    }
+
+    #[test]
+    fn remove_day() {
+        assert_eq!(
+            remove(LIB, 2024, 3).unwrap().unwrap(),
+            include_str!("../tests/fixtures/registry/remove.rs")
+        );
+        assert_eq!(remove(LIB, 2024, 2).unwrap(), None);
+        assert_eq!(remove(LIB, 2023, 1).unwrap(), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        for (year, day) in [(2024, 2), (2023, 5), (2024, 25), (2026, 1)] {
+            let added = add(LIB, year, day).unwrap().unwrap();
+            assert_eq!(remove(&added, year, day).unwrap().unwrap(), LIB);
+        }
+        // Removing the last day of a year removes the year.
+        let removed = remove(LIB, 2025, 1).unwrap().unwrap();
+        assert!(!removed.contains("y2025"));
+        assert_eq!(add(&removed, 2025, 1).unwrap().unwrap(), LIB);
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        let lib = LIB.replace('\n', "\r\n");
+        let added = add(&lib, 2024, 2).unwrap().unwrap();
+        assert!(added.contains("    d01 = \"01.rs\",\r\n        d02 = \"02.rs\",\r\n"));
+        assert!(!added.replace("\r\n", "").contains('\n'));
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+
+use crate::input_dir;
+
+/// Returns the input file of the day, in the input directory of the selected
+/// profile.
+pub fn day_input(year: u32, day: u32) -> PathBuf {
+    input_dir().join(format!("{year:04}/{day:02}.txt"))
+}
+
+/// Returns a Cargo command for the solutions: `run` builds the `aoc` binary
+/// and `test` the library's tests, to be given the day with [`day_args`] or
+/// [`test_filter`]. The solution reads its input from `input`, a path or `-`
+/// for stdin, if given, which only commands that build leave out.
+pub fn cargo(cmd: &str, input: Option<&OsStr>) -> process::Command {
+    let mut command = process::Command::new(env!("CARGO"));
+    command.args([cmd, "--release"]);
+    if let Some(input) = input {
+        command.env("ADVENT_INPUT", input);
+    }
+    match cmd {
+        "run" => command.args(["--bin", "aoc"]),
+        "test" => command.arg("--lib"),
+        _ => &mut command,
+    };
+    command
+}
+
+/// The arguments selecting the day for the `aoc` binary.
+pub fn day_args(year: u32, day: u32) -> [String; 2] {
+    [year.to_string(), day.to_string()]
+}
+
+/// The test name filter selecting the tests of the day.
+pub fn test_filter(year: u32, day: u32) -> String {
+    format!("y{year:04}::d{day:02}::")
+}
+
+/// Runs the solution for the given day on its input file and returns its
+/// output.
+pub fn run(year: u32, day: u32, args: &[&str]) -> Result<String> {
+    let output = cargo("run", Some(day_input(year, day).as_os_str()))
+        .args(["--quiet", "--"])
+        .args(day_args(year, day))
+        .args(args)
+        .stderr(process::Stdio::inherit())
+        .output()?;
+
+    if !output.status.success() {
+        bail!("{year:04}/{day:02} exited with {}", output.status);
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Runs the ignored `default` test of the day against its real input and
+/// returns whether it passed.
+pub fn test_default(year: u32, day: u32) -> Result<bool> {
+    let test = format!("{}tests::default", test_filter(year, day));
+    let status = cargo("test", Some(day_input(year, day).as_os_str()))
+        .args(["--quiet", "--", "--ignored", "--exact", &test])
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null())
+        .status()?;
+    Ok(status.success())
+}
+
+/// The result of a single part as printed by a solution binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    pub answer: String,
+    pub time: Option<Duration>,
+}
+
+/// Everything of interest printed by a solution binary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Output {
+    pub parse: Option<Duration>,
+    pub parts: BTreeMap<u32, Part>,
+}
+
+/// Extracts the answers and timings from the output of a solution binary.
+///
+/// The runner prints one `Part N: <answer>` line per part, optionally
+/// followed by the elapsed time in parentheses, and may print the time spent
+/// parsing the input on a `Parse` line.
+pub fn parse_output(output: &str) -> Output {
+    let mut out = Output::default();
+    for line in output.lines() {
+        let line = strip_ansi(line);
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("Parse") {
+            out.parse = trailing_duration(rest).and_then(|(_, time)| time);
+        } else if let Some((part, answer)) = line
+            .strip_prefix("Part ")
+            .and_then(|rest| rest.split_once(':'))
+        {
+            let (answer, time) = trailing_duration(answer).unwrap_or((answer, None));
+            let answer = answer.trim().to_owned();
+            if let (Ok(part), false) = (part.trim().parse(), answer.is_empty()) {
+                out.parts.insert(part, Part { answer, time });
+            }
+        }
+    }
+    out
+}
+
+/// Splits a trailing duration in parentheses, e.g. `(1.5ms)`, from the text.
+fn trailing_duration(s: &str) -> Option<(&str, Option<Duration>)> {
+    let (rest, time) = s.trim_end().strip_suffix(')')?.rsplit_once('(')?;
+    Some((rest, Some(parse_duration(time)?)))
+}
+
+pub fn parse_answers(output: &str) -> BTreeMap<u32, String> {
+    parse_output(output)
+        .parts
+        .into_iter()
+        .map(|(part, Part { answer, .. })| (part, answer))
+        .collect()
+}
+
+/// Parses durations like `1.5ms` or `22.708µs ± 1.1µs`, ignoring any spread.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.split_whitespace().next()?;
+    let i = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(i);
+    let value: f64 = value.parse().ok()?;
+    let nanos = match unit {
+        "ns" => value,
+        "µs" | "us" => value * 1e3,
+        "ms" => value * 1e6,
+        "s" => value * 1e9,
+        _ => return None,
+    };
+    Some(Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Removes terminal escape sequences from a line of output.
+pub fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip until the final byte of the CSI sequence
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers() {
+        let output = "\
+Part 1: 5208 (1.234ms)
+\x1b[1mPart 2\x1b[0m: 1972 (2.5s)
+";
+        let answers = parse_answers(output);
+        assert_eq!(answers[&1], "5208");
+        assert_eq!(answers[&2], "1972");
+    }
+
+    #[test]
+    fn timings() {
+        let output = parse_output("Part 1: 42 (1.5ms)\nPart 2: 7 (22.708µs ± 1.1µs)\n");
+        assert_eq!(output.parts[&1].time, Some(Duration::from_micros(1500)));
+        assert_eq!(output.parts[&2].time, Some(Duration::from_nanos(22708)));
+        assert_eq!(output.parse, None);
+
+        let output = parse_output("Parse: (3ms)\nPart 1: 42 (1.5ms)\n");
+        assert_eq!(output.parse, Some(Duration::from_millis(3)));
+    }
+
+    #[test]
+    fn answers_without_timing() {
+        let answers = parse_answers("Part 1: hello world\nnoise\nPart 2: (1, 2)\n");
+        assert_eq!(answers[&1], "hello world");
+        assert_eq!(answers[&2], "(1, 2)");
+    }
+}
@@ -0,0 +1,158 @@
+//! The progress dashboard: stars from the site next to the local state of
+//! each day.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use yansi::Paint;
+
+use crate::html;
+
+/// Where the puzzle input of a day is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Present,
+    /// Only in the encrypted store.
+    Locked,
+    Missing,
+}
+
+/// The progress on a released day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day {
+    pub day: u32,
+    /// The stars earned, `None` if the calendar could not be downloaded.
+    pub stars: Option<u32>,
+    /// Whether the solution is registered.
+    pub registered: bool,
+    pub input: Input,
+    /// Whether the `default` test passed, `None` if it was not run.
+    pub test: Option<bool>,
+    /// The number of parts with a recorded answer.
+    pub answers: u32,
+    /// The number of parts the puzzle has.
+    pub parts: u32,
+}
+
+impl Day {
+    /// Returns whether nothing is left to do for the day.
+    pub fn is_finished(&self) -> bool {
+        self.stars.is_none_or(|s| s == 2)
+            && self.registered
+            && self.input != Input::Missing
+            && self.test != Some(false)
+            && self.answers == self.parts
+    }
+}
+
+/// Returns the stars earned per day from the calendar page of a year.
+///
+/// Days without stars are left out.
+pub fn stars(page: &str) -> BTreeMap<u32, u32> {
+    let nodes = html::parse(page);
+    let days = html::find_all(&nodes, &|e| {
+        e.name == "a" && e.attr("href").is_some_and(|h| h.contains("/day/"))
+    });
+    days.into_iter()
+        .filter_map(|e| {
+            let day = e.attr("href")?.rsplit('/').next()?.parse().ok()?;
+            let label = e.attr("aria-label").unwrap_or_default();
+            let stars = if e.has_class("calendar-verycomplete") || label.ends_with("two stars") {
+                2
+            } else if e.has_class("calendar-complete") || label.ends_with("one star") {
+                1
+            } else {
+                return None;
+            };
+            Some((day, stars))
+        })
+        .collect()
+}
+
+/// Renders a table with a row per released day.
+pub fn render(days: &[Day], color: bool) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:>3}  {:<5}  {:<8}  {:<7}  {:<4}  Answers",
+        "Day", "Stars", "Solution", "Input", "Test"
+    )
+    .unwrap();
+    for day in days {
+        let stars = match day.stars {
+            Some(2) => "**",
+            Some(1) => "*",
+            Some(_) => "",
+            None => "?",
+        };
+        let registered = if day.registered { "yes" } else { "-" };
+        let input = match day.input {
+            Input::Present => "yes",
+            Input::Locked => "locked",
+            Input::Missing => "-",
+        };
+        let test = match day.test {
+            Some(true) => "pass",
+            Some(false) => "FAIL",
+            None => "-",
+        };
+        let answers = format!("{}/{}", day.answers, day.parts);
+        let mut row = format!(
+            "{:>3}  {stars:<5}  {registered:<8}  {input:<7}  {test:<4}  {answers}",
+            day.day
+        );
+        if color {
+            row = match (day.test, day.is_finished()) {
+                (Some(false), _) => row.red().to_string(),
+                (_, true) => row.yellow().to_string(),
+                (_, false) => row,
+            };
+        }
+        writeln!(out, "{}", row.trim_end()).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stars() {
+        let page = include_str!("../tests/fixtures/2024/calendar.html");
+        assert_eq!(stars(page), BTreeMap::from([(1, 2), (2, 1)]));
+        assert!(stars("<html></html>").is_empty());
+    }
+
+    #[test]
+    fn renders() {
+        let day = |day, stars, registered, input, test, answers| Day {
+            day,
+            stars,
+            registered,
+            input,
+            test,
+            answers,
+            parts: 2,
+        };
+        let days = [
+            day(1, Some(2), true, Input::Present, Some(true), 2),
+            day(2, Some(1), true, Input::Locked, None, 1),
+            day(3, Some(0), false, Input::Missing, None, 0),
+            day(4, None, true, Input::Present, Some(false), 2),
+        ];
+        assert!(days[0].is_finished());
+        assert!(!days[1].is_finished());
+        assert!(!days[3].is_finished());
+        assert_eq!(
+            render(&days, false),
+            "\
+Day  Stars  Solution  Input    Test  Answers
+  1  **     yes       yes      pass  2/2
+  2  *      yes       locked   -     1/2
+  3         -         -        -     0/2
+  4  ?      yes       yes      FAIL  2/2
+"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::answers::Reason;
+
+/// The site's response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(Reason),
+    RateLimited(Option<Duration>),
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => f.write_str("correct"),
+            Self::Wrong(reason) => reason.fmt(f),
+            Self::RateLimited(Some(wait)) => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+            Self::RateLimited(None) => f.write_str("rate limited"),
+            Self::AlreadySolved => f.write_str("already solved"),
+            Self::Unknown(text) => write!(f, "unrecognized response: {text}"),
+        }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooHigh => f.write_str("too high"),
+            Self::TooLow => f.write_str("too low"),
+            Self::Incorrect => f.write_str("incorrect"),
+        }
+    }
+}
+
+/// Parses the HTML page returned after posting an answer.
+pub fn parse_verdict(html: &str) -> Verdict {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Verdict::Wrong(Reason::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Verdict::Wrong(Reason::TooLow)
+        } else {
+            Verdict::Wrong(Reason::Incorrect)
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::RateLimited(parse_wait(&text))
+    } else if text.contains("Did you already complete it?") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+/// Extracts the text of the `<article>` element with tags removed.
+fn article_text(html: &str) -> String {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..]
+        .find("</article>")
+        .map_or(html.len(), |i| start + i);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses e.g. "You have 1m 5s left to wait" into a duration.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    let mut secs = 0;
+    for part in text[start..end].split_whitespace() {
+        let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+        secs += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(body: &str) -> String {
+        format!("<html><body><main><article><p>{body}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn verdicts() {
+        assert_eq!(
+            parse_verdict(&page(
+                "That's the right answer! You are <em>one gold star</em> closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too high. \
+                 Please wait one minute before trying again."
+            )),
+            Verdict::Wrong(Reason::TooHigh)
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Verdict::Wrong(Reason::TooLow)
+        );
+        assert_eq!(
+            parse_verdict(&page("That's not the right answer.")),
+            Verdict::Wrong(Reason::Incorrect)
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "You don't seem to be solving the right level. \
+                 Did you already complete it?"
+            )),
+            Verdict::AlreadySolved
+        );
+    }
+
+    #[test]
+    fn rate_limited() {
+        assert_eq!(
+            parse_verdict(&page(
+                "You gave an answer too recently; you have to wait after \
+                 submitting an answer before trying again. You have 1m 5s \
+                 left to wait."
+            )),
+            Verdict::RateLimited(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            parse_verdict(&page("You gave an answer too recently.")),
+            Verdict::RateLimited(None)
+        );
+    }
+}
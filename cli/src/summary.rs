@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::runner::{self, Output, Part};
+use crate::{check_input, error, print, split_args, warning};
+
+/// The outcome of running a single day.
+struct Row {
+    year: u32,
+    day: u32,
+    output: Option<Output>,
+}
+
+impl Row {
+    fn total(&self) -> Duration {
+        let Some(output) = &self.output else {
+            return Duration::ZERO;
+        };
+        let parts = output.parts.values().filter_map(|p| p.time);
+        output.parse.into_iter().chain(parts).sum()
+    }
+}
+
+/// Runs every given day and prints a combined table of answers and timings.
+///
+/// Every input is fetched or decrypted before the first day runs. Returns
+/// whether every day ran successfully.
+pub fn run_all(days: &[(u32, u32)], args: &[&str]) -> Result<bool> {
+    let checked: Vec<_> = days
+        .iter()
+        .map(|&(year, day)| check_input(year, day))
+        .collect();
+    let mut rows = Vec::new();
+    for (&(year, day), checked) in days.iter().zip(checked) {
+        let output = checked
+            .and_then(|()| runner::run(year, day, args))
+            .map(|output| runner::parse_output(&output));
+        if let Err(err) = &output {
+            warning("Errored", format!("{year:04}/{day:02}: {err:#}"));
+        }
+        rows.push(Row {
+            year,
+            day,
+            output: output.ok(),
+        });
+    }
+
+    print_table(&rows);
+
+    let failed = rows.iter().filter(|r| r.output.is_none()).count();
+    if failed > 0 {
+        error("Failed", format!("{failed} of {} days", rows.len()));
+    }
+    Ok(failed == 0)
+}
+
+/// Runs the tests of every given day one after another.
+///
+/// Returns whether every day's tests passed.
+pub fn test_all(days: &[(u32, u32)], args: &[String]) -> Result<bool> {
+    let (cargo_args, test_args) = split_args(args);
+    let mut failed = Vec::new();
+    for &(year, day) in days {
+        let ok = check_input(year, day).is_ok()
+            && runner::cargo("test", Some(runner::day_input(year, day).as_os_str()))
+                .args(cargo_args)
+                .args(["--", &runner::test_filter(year, day)])
+                .args(test_args)
+                .status()?
+                .success();
+        if !ok {
+            failed.push(format!("{year:04}/{day:02}"));
+        }
+    }
+
+    if failed.is_empty() {
+        print("Tested", format!("{} days", days.len()));
+    } else {
+        error(
+            "Failed",
+            format!(
+                "{} of {} days: {}",
+                failed.len(),
+                days.len(),
+                failed.join(", ")
+            ),
+        );
+    }
+    Ok(failed.is_empty())
+}
+
+fn print_table(rows: &[Row]) {
+    let cell = |row: &Row, part: u32| -> (String, String) {
+        match &row.output {
+            Some(output) => match output.parts.get(&part) {
+                Some(Part { answer, time }) => (answer.clone(), fmt_time(*time)),
+                None => ("-".into(), "".into()),
+            },
+            None => ("ERROR".into(), "".into()),
+        }
+    };
+    let cells: Vec<_> = rows.iter().map(|r| [cell(r, 1), cell(r, 2)]).collect();
+    let width = |i: usize| {
+        cells
+            .iter()
+            .map(|c| c[i].0.chars().count())
+            .chain([6])
+            .max()
+            .unwrap()
+    };
+    let (w1, w2) = (width(0), width(1));
+
+    println!(
+        "{:<9}{:<w1$}  {:>10}  {:<w2$}  {:>10}",
+        "Day", "Part 1", "Time", "Part 2", "Time"
+    );
+    for (row, [(a1, t1), (a2, t2)]) in rows.iter().zip(&cells) {
+        println!(
+            "{:04}/{:02}  {a1:<w1$}  {t1:>10}  {a2:<w2$}  {t2:>10}",
+            row.year, row.day
+        );
+    }
+
+    let mut totals = BTreeMap::<u32, Duration>::new();
+    for row in rows {
+        *totals.entry(row.year).or_default() += row.total();
+    }
+    for (year, total) in totals {
+        print("Total", format!("{year:04} in {}", fmt_time(Some(total))));
+    }
+}
+
+fn fmt_time(time: Option<Duration>) -> String {
+    time.map(|t| format!("{t:.2?}")).unwrap_or_default()
+}
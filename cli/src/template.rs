@@ -1,47 +1,226 @@
-use advent::prelude::*;
+//! The templates `new` creates solutions from.
+//!
+//! Templates are Rust sources with `{ name }` placeholders. A line holding
+//! only `{ if name }`, `{ else }` or `{ end }` keeps or drops the lines up to
+//! the next one depending on whether the variable is empty, and `{{ ` writes
+//! a literal `{ `.
 
-type Input = Vec<i64>;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
 
-fn parse_input(input: &str) -> Input {
-    todo!("parsing")
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::config;
+
+/// The templates that come with the CLI.
+const BUILTIN: &[(&str, &str)] = &[
+    ("default", include_str!("../templates/default.rs")),
+    ("graph", include_str!("../templates/graph.rs")),
+    ("grid", include_str!("../templates/grid.rs")),
+    ("vm", include_str!("../templates/vm.rs")),
+];
+
+/// Returns the template with the given name, from `templates` in the config,
+/// the built-in ones, or else a path relative to the workspace.
+pub fn load(name: &str, workspace_dir: &Path) -> Result<String> {
+    let path = match config::get().templates.get(name) {
+        Some(path) => path.as_path(),
+        None => match BUILTIN.iter().find(|(n, _)| *n == name) {
+            Some((_, template)) => return Ok((*template).to_owned()),
+            None => Path::new(name),
+        },
+    };
+    if !path.to_string_lossy().ends_with(".rs") {
+        let mut names: Vec<_> = BUILTIN.iter().map(|(n, _)| *n).collect();
+        names.extend(config::get().templates.keys().map(String::as_str));
+        names.sort();
+        names.dedup();
+        bail!(
+            "unknown template `{name}`, expected one of: {}, or a path to a `.rs` file",
+            names.join(", ")
+        );
+    }
+    fs::read_to_string(workspace_dir.join(path))
+        .with_context(|| format!("failed to read template `{}`", path.display()))
 }
 
-fn default_input() -> Input {
-    #[cfg(feature = "default-inputs")]
-    return parse_input(include_input!({ year } / { day }));
-    #[cfg(not(feature = "default-inputs"))]
-    panic!("default-inputs feature not enabled");
+/// Whether the lines inside an `{ if }` block are kept.
+struct Block {
+    condition: bool,
+    otherwise: bool,
+    line: usize,
 }
 
-fn part1(input: Input) -> i64 {
-    todo!("part 1")
+/// Renders a template with the given variables.
+pub fn render(template: &str, vars: &BTreeMap<&str, String>) -> Result<String> {
+    let mut out = String::new();
+    let mut blocks: Vec<Block> = Vec::new();
+    for (n, line) in (1..).zip(template.split_inclusive('\n')) {
+        let directive = line
+            .trim()
+            .strip_prefix("{ ")
+            .and_then(|d| d.strip_suffix(" }"))
+            .map(|d| d.split_whitespace().collect::<Vec<_>>());
+        match directive.as_deref() {
+            Some(["if", name]) => {
+                blocks.push(Block {
+                    condition: !lookup(vars, name, n)?.is_empty(),
+                    otherwise: false,
+                    line: n,
+                });
+                continue;
+            }
+            Some(["else"]) => {
+                match blocks.last_mut() {
+                    Some(block) if !block.otherwise => block.otherwise = true,
+                    _ => bail!("`{{ else }}` without `{{ if }}` on line {n} of the template"),
+                }
+                continue;
+            }
+            Some(["end"]) => {
+                if blocks.pop().is_none() {
+                    bail!("`{{ end }}` without `{{ if }}` on line {n} of the template");
+                }
+                continue;
+            }
+            // A variable on a line of its own that holds whole lines, like
+            // the example tests, replaces the line.
+            Some([name]) => {
+                let value = lookup(vars, name, n)?;
+                if value.is_empty() || value.ends_with('\n') {
+                    if blocks.iter().all(|b| b.condition != b.otherwise) {
+                        out.push_str(value);
+                    }
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        if blocks.iter().all(|b| b.condition != b.otherwise) {
+            out.push_str(&substitute(line, vars, n)?);
+        }
+    }
+    if let Some(block) = blocks.last() {
+        bail!(
+            "`{{ if }}` without `{{ end }}` on line {} of the template",
+            block.line
+        );
+    }
+    Ok(out)
 }
 
-fn part2(input: Input) -> i64 {
-    todo!("part 2")
+fn lookup<'a>(vars: &'a BTreeMap<&str, String>, name: &str, line: usize) -> Result<&'a str> {
+    vars.get(name)
+        .map(String::as_str)
+        .ok_or_else(|| anyhow!("unknown variable `{name}` on line {line} of the template"))
 }
 
-fn main() {
-    let solution = advent::new(default_input).part(part1).part(part2).build();
-    solution.cli()
+fn substitute(line: &str, vars: &BTreeMap<&str, String>, n: usize) -> Result<String> {
+    let mut out = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("{ ") {
+        let after = &rest[start + 2..];
+        if rest[..start].ends_with('{') {
+            out.push_str(&rest[..start]);
+            out.push(' ');
+            rest = after;
+            continue;
+        }
+        let len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(after.len());
+        let (name, tail) = after.split_at(len);
+        match tail.strip_prefix(" }") {
+            Some(tail) if !name.is_empty() => {
+                out.push_str(&rest[..start]);
+                out.push_str(lookup(vars, name, n)?);
+                rest = tail;
+            }
+            _ => {
+                out.push_str(&rest[..start + 2]);
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[ignore]
+    fn vars() -> BTreeMap<&'static str, String> {
+        BTreeMap::from([
+            ("year", "2024".into()),
+            ("day", "06".into()),
+            ("title", String::new()),
+            ("examples", "        one();\n        two();\n".into()),
+        ])
+    }
+
+    #[test]
+    fn renders() {
+        let template = "\
+{ if title }
+//! { title }
+{ else }
+//! Day { day }
+{ end }
+include_input!({ year } / { day })
+fn examples() {
+{ examples }
+}
+let p = Point {{ x };
+";
+        assert_eq!(
+            render(template, &vars()).unwrap(),
+            "\
+//! Day 06
+include_input!(2024 / 06)
+fn examples() {
+        one();
+        two();
+}
+let p = Point { x };
+"
+        );
+    }
+
     #[test]
-    fn default() {
-        let input = default_input();
-        assert_eq!(part1(input.clone()), 1);
-        assert_eq!(part2(input), 2);
+    fn errors() {
+        let err = |template| render(template, &vars()).unwrap_err().to_string();
+        assert_eq!(
+            err("a\n{ yaer }\n"),
+            "unknown variable `yaer` on line 2 of the template"
+        );
+        assert!(err("{ if title }\n").contains("without `{ end }` on line 1"));
+        assert!(err("{ end }\n").contains("without `{ if }` on line 1"));
+        assert!(err("{ if day }\n{ else }\n{ else }\n{ end }\n").contains("line 3"));
     }
 
     #[test]
-    fn examples() {
-        let input = parse_input("");
-        assert_eq!(part1(input.clone()), 1);
-        assert_eq!(part2(input), 2);
+    fn builtin() {
+        let mut vars = vars();
+        vars.extend([
+            ("title", "Day 6: Guard Gallivant".into()),
+            ("url", "https://adventofcode.com/2024/day/6".into()),
+            ("example", "..#\n".into()),
+            ("part1_answer", "41".into()),
+            ("part2_answer", "6".into()),
+        ]);
+        for (name, template) in BUILTIN {
+            let rendered = render(template, &vars).unwrap();
+            assert!(
+                rendered.starts_with("//! Day 6: Guard Gallivant\n"),
+                "{name}"
+            );
+            assert!(
+                rendered.contains("solution!(2024 / 06, part1, part2);"),
+                "{name}"
+            );
+            assert!(!rendered.contains("{ "), "{name}");
+        }
     }
 }
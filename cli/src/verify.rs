@@ -0,0 +1,151 @@
+use std::io;
+use std::io::IsTerminal;
+
+use anyhow::Result;
+use yansi::Paint;
+
+use crate::answers::{self, Answers};
+use crate::{check_input, config, error, print, runner, warning, workspace_dir};
+
+/// The outcome of checking one part against the answers ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing(String),
+    NoAnswer,
+    Error,
+}
+
+impl Status {
+    fn new(expected: Option<&str>, actual: Option<&str>) -> Self {
+        match (expected, actual) {
+            (Some(e), Some(a)) if e == a => Self::Pass,
+            (Some(e), a) => Self::Fail {
+                expected: e.into(),
+                actual: a.unwrap_or("nothing").into(),
+            },
+            (None, Some(a)) => Self::Missing(a.into()),
+            (None, None) => Self::NoAnswer,
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Self::Pass => "pass".into(),
+            Self::Fail { expected, actual } => {
+                format!("FAIL: expected {expected}, got {actual}")
+            }
+            Self::Missing(_) => "missing".into(),
+            Self::NoAnswer => "-".into(),
+            Self::Error => "ERROR".into(),
+        }
+    }
+
+    fn paint(&self, text: &str) -> String {
+        if !io::stdout().is_terminal() {
+            return text.into();
+        }
+        match self {
+            Self::Pass => text.green().to_string(),
+            Self::Fail { .. } | Self::Error => text.red().bold().to_string(),
+            Self::Missing(_) => text.yellow().to_string(),
+            Self::NoAnswer => text.dim().to_string(),
+        }
+    }
+}
+
+/// Runs every given day and compares its output with the answers ledger.
+///
+/// Every input is fetched or decrypted before the first day runs. With
+/// `record` set, answers for parts missing from the ledger are stored.
+/// Returns whether every recorded answer was reproduced.
+pub fn verify(days: &[(u32, u32)], record: bool) -> Result<bool> {
+    let answers_path = answers::path(&workspace_dir(), config::profile());
+    let mut answers = Answers::load(&answers_path)?;
+    let mut rows = Vec::new();
+
+    let checked: Vec<_> = days
+        .iter()
+        .map(|&(year, day)| check_input(year, day))
+        .collect();
+    for (&(year, day), checked) in days.iter().zip(checked) {
+        let output = match checked.and_then(|()| runner::run(year, day, &[])) {
+            Ok(output) => Some(runner::parse_answers(&output)),
+            Err(err) => {
+                warning("Errored", format!("{year:04}/{day:02}: {err:#}"));
+                None
+            }
+        };
+        let statuses = [1, 2].map(|part| {
+            let Some(output) = &output else {
+                return Status::Error;
+            };
+            let expected = answers
+                .get(year, day, part)
+                .and_then(|p| p.answer.as_deref());
+            Status::new(expected, output.get(&part).map(String::as_str))
+        });
+        if record {
+            for (part, status) in (1..=2).zip(&statuses) {
+                if let Status::Missing(actual) = status {
+                    answers.entry(year, day, part).answer = Some(actual.clone());
+                }
+            }
+        }
+        rows.push(((year, day), statuses));
+    }
+
+    println!("{:<9}{:<10}Part 2", "Day", "Part 1");
+    for ((year, day), [part1, part2]) in &rows {
+        println!(
+            "{year:04}/{day:02}  {}{}",
+            part1.paint(&format!("{:<10}", part1.text())),
+            part2.paint(&part2.text())
+        );
+    }
+
+    let statuses: Vec<_> = rows.iter().flat_map(|(_, s)| s).collect();
+    let count = |f: fn(&Status) -> bool| statuses.iter().filter(|s| f(s)).count();
+    let passed = count(|s| matches!(s, Status::Pass));
+    let failed = count(|s| matches!(s, Status::Fail { .. } | Status::Error));
+    let missing = count(|s| matches!(s, Status::Missing(_)));
+    let summary = format!("{passed} passed, {failed} failed, {missing} missing");
+
+    if record && missing > 0 {
+        answers.save(&answers_path)?;
+        print("Recorded", format!("{missing} answers in `answers.toml`"));
+    }
+    if failed > 0 {
+        error("Failed", summary);
+    } else {
+        print("Verified", summary);
+    }
+    Ok(failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status() {
+        assert_eq!(Status::new(Some("1"), Some("1")), Status::Pass);
+        assert_eq!(
+            Status::new(Some("1"), Some("2")),
+            Status::Fail {
+                expected: "1".into(),
+                actual: "2".into()
+            }
+        );
+        assert_eq!(
+            Status::new(Some("1"), None),
+            Status::Fail {
+                expected: "1".into(),
+                actual: "nothing".into()
+            }
+        );
+        assert_eq!(Status::new(None, Some("2")), Status::Missing("2".into()));
+        assert_eq!(Status::new(None, None), Status::NoAnswer);
+    }
+}
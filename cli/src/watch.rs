@@ -0,0 +1,380 @@
+//! Rebuilding and rerunning a day whenever its files change.
+//!
+//! Files are polled for changes, as a day only has a handful of them. The
+//! test and solution executables are run directly rather than through Cargo,
+//! so that a change in the middle of a run can kill them without leaving
+//! anything behind. A Ctrl-C reaches Cargo and the executables as well, as
+//! they stay in the terminal's process group.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::{self, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::{check_input, error, print, runner, warning};
+
+/// How often the watched files are checked for changes.
+const POLL: Duration = Duration::from_millis(200);
+
+/// The modification times of the watched files, `None` for missing ones.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Takes a snapshot of the files and of the files in the directories.
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        match fs::read_dir(path) {
+            Ok(entries) => {
+                for entry in entries.flatten() {
+                    let modified = entry.metadata().and_then(|m| m.modified()).ok();
+                    snapshot.insert(entry.path(), modified);
+                }
+            }
+            Err(_) => {
+                let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+                snapshot.insert(path.clone(), modified);
+            }
+        }
+    }
+    snapshot
+}
+
+/// Reruns the day every time the solution, its input or its examples change.
+pub fn watch(year: u32, day: u32, workspace_dir: &Path, paths: &[PathBuf]) -> Result<()> {
+    let clear = io::stdout().is_terminal();
+    let mut last: Option<BTreeMap<u32, String>> = None;
+    loop {
+        let before = snapshot(paths);
+        let changed = || snapshot(paths) != before;
+        if clear {
+            print!("\x1b[2J\x1b[H");
+        }
+        print(
+            "Watching",
+            format!("{year:04}/{day:02}, press Ctrl-C to stop"),
+        );
+
+        if let Some(answers) = run_once(year, day, workspace_dir, &changed)? {
+            for (header, message) in diff(last.as_ref(), &answers) {
+                match header {
+                    "Changed" | "Missing" => warning(header, message),
+                    _ => print(header, message),
+                }
+            }
+            last = Some(answers);
+        }
+
+        while !changed() {
+            thread::sleep(POLL);
+        }
+    }
+}
+
+/// Builds the day, runs its examples test and then the solution.
+///
+/// Returns the answers, or `None` if a step failed or was interrupted by a
+/// change.
+fn run_once(
+    year: u32,
+    day: u32,
+    workspace_dir: &Path,
+    changed: &impl Fn() -> bool,
+) -> Result<Option<BTreeMap<u32, String>>> {
+    // Builds are left to finish, as Cargo and rustc would be hard to stop
+    // cleanly, and a change during one starts over right after.
+    let Some(test) = build(year, day, workspace_dir, true)? else {
+        return Ok(None);
+    };
+    let Some(bin) = build(year, day, workspace_dir, false)? else {
+        return Ok(None);
+    };
+    if changed() {
+        return Ok(None);
+    }
+
+    let mut tests = process::Command::new(test);
+    let name = format!("{}tests::examples", runner::test_filter(year, day));
+    tests.args(["--exact", &name]).current_dir(workspace_dir);
+    match run_until_changed(&mut tests, false, changed)? {
+        Some((status, _)) if status.success() => print("Passed", "examples"),
+        Some(_) => {
+            error("Failed", "examples");
+            return Ok(None);
+        }
+        None => return Ok(None),
+    }
+
+    if let Err(err) = check_input(year, day) {
+        error("Failed", format!("{year:04}/{day:02}: {err:#}"));
+        return Ok(None);
+    }
+    let mut solution = process::Command::new(bin);
+    solution
+        .args(runner::day_args(year, day))
+        .current_dir(workspace_dir);
+    solution.env("ADVENT_INPUT", runner::day_input(year, day));
+    match run_until_changed(&mut solution, true, changed)? {
+        Some((status, output)) if status.success() => Ok(Some(runner::parse_answers(&output))),
+        Some((status, _)) => {
+            error("Failed", format!("{year:04}/{day:02} exited with {status}"));
+            Ok(None)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Builds the `aoc` binary or the tests of the solutions for the day and
+/// returns the executable, or prints the errors and returns `None` if they
+/// failed to compile.
+fn build(year: u32, day: u32, workspace_dir: &Path, tests: bool) -> Result<Option<PathBuf>> {
+    let input = runner::day_input(year, day);
+    let mut cargo = match tests {
+        true => runner::cargo("test", Some(input.as_os_str())),
+        false => runner::cargo("build", Some(input.as_os_str())),
+    };
+    match tests {
+        true => cargo.arg("--no-run"),
+        false => cargo.args(["--bin", "aoc"]),
+    };
+    let output = cargo
+        .arg("--message-format=json")
+        .current_dir(workspace_dir)
+        .stdin(Stdio::null())
+        .output()?;
+    let messages = String::from_utf8_lossy(&output.stdout);
+    if output.status.success()
+        && let Some(executable) = executable(&messages, tests)
+    {
+        return Ok(Some(executable));
+    }
+
+    let errors = errors(&messages);
+    if errors.is_empty() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+    for line in &errors {
+        println!("{line}");
+    }
+    error(
+        "Failed",
+        format!(
+            "to compile the solutions{}",
+            match errors.len() {
+                0 => String::new(),
+                1 => " with 1 error".to_owned(),
+                n => format!(" with {n} errors"),
+            }
+        ),
+    );
+    Ok(None)
+}
+
+/// Runs the command until it exits or the files change, in which case it is
+/// killed. Returns its exit status, and its output if it was captured, or
+/// `None` if it was killed.
+fn run_until_changed(
+    command: &mut process::Command,
+    capture: bool,
+    changed: &impl Fn() -> bool,
+) -> Result<Option<(process::ExitStatus, String)>> {
+    if capture {
+        command.stdout(Stdio::piped());
+    }
+    let mut child = command.stdin(Stdio::null()).spawn()?;
+
+    // Echo the output as it comes while keeping it for the answers.
+    let reader = child.stdout.take().map(|stdout| {
+        thread::spawn(move || {
+            let mut output = String::new();
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                println!("{line}");
+                output.push_str(&line);
+                output.push('\n');
+            }
+            output
+        })
+    });
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if changed() {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(POLL);
+    };
+    let output = match reader {
+        Some(reader) => reader.join().unwrap(),
+        None => String::new(),
+    };
+    Ok(status.map(|status| (status, output)))
+}
+
+/// A line of `--message-format=json` output from Cargo.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum Event {
+    CompilerArtifact {
+        profile: Profile,
+        executable: Option<PathBuf>,
+    },
+    CompilerMessage {
+        message: Diagnostic,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct Profile {
+    test: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct Diagnostic {
+    message: String,
+    level: String,
+    code: Option<Code>,
+    spans: Vec<Span>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Code {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Span {
+    file_name: String,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+}
+
+fn messages(json: &str) -> impl Iterator<Item = Event> + '_ {
+    json.lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+}
+
+/// Returns the test or binary executable built, from Cargo's JSON messages.
+fn executable(json: &str, tests: bool) -> Option<PathBuf> {
+    messages(json).find_map(|message| match message {
+        Event::CompilerArtifact {
+            profile,
+            executable,
+        } if profile.test == tests => executable,
+        _ => None,
+    })
+}
+
+/// Returns the compile errors from Cargo's JSON messages, one line each.
+fn errors(json: &str) -> Vec<String> {
+    messages(json)
+        .filter_map(|message| {
+            let Event::CompilerMessage { message } = message else {
+                return None;
+            };
+            // Summaries like "aborting due to 2 previous errors" have no span.
+            let span = message.spans.iter().find(|s| s.is_primary)?;
+            if message.level != "error" {
+                return None;
+            }
+            let code = message
+                .code
+                .map(|c| format!("[{}]", c.code))
+                .unwrap_or_default();
+            Some(format!(
+                "{}:{}:{}: error{code}: {}",
+                span.file_name, span.line_start, span.column_start, message.message
+            ))
+        })
+        .collect()
+}
+
+/// Compares the answers with those of the previous run, as status headers
+/// and messages.
+fn diff(
+    previous: Option<&BTreeMap<u32, String>>,
+    current: &BTreeMap<u32, String>,
+) -> Vec<(&'static str, String)> {
+    let mut parts: Vec<u32> = current.keys().copied().collect();
+    parts.extend(previous.into_iter().flat_map(|p| p.keys().copied()));
+    parts.sort();
+    parts.dedup();
+    parts
+        .into_iter()
+        .map(|part| {
+            let before = previous.and_then(|p| p.get(&part));
+            match (before, current.get(&part)) {
+                (None, Some(answer)) => ("Answer", format!("part {part}: {answer}")),
+                (Some(before), Some(answer)) if before == answer => {
+                    ("Unchanged", format!("part {part}: {answer}"))
+                }
+                (Some(before), Some(answer)) => {
+                    ("Changed", format!("part {part}: {answer}, was {before}"))
+                }
+                (Some(before), None) => ("Missing", format!("part {part}, was {before}")),
+                (None, None) => unreachable!("part {part} is in neither run"),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGES: &str = r#"{"reason":"compiler-artifact","profile":{"test":false},"executable":null}
+{"reason":"compiler-message","message":{"message":"unused variable: `x`","level":"warning","code":{"code":"unused_variables"},"spans":[{"file_name":"2024/06.rs","line_start":3,"column_start":9,"is_primary":true}]}}
+{"reason":"compiler-message","message":{"message":"mismatched types","level":"error","code":{"code":"E0308"},"spans":[{"file_name":"2024/06.rs","line_start":12,"column_start":5,"is_primary":false},{"file_name":"2024/06.rs","line_start":14,"column_start":7,"is_primary":true}]}}
+{"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","level":"error","code":null,"spans":[]}}
+{"reason":"compiler-artifact","profile":{"test":true},"executable":"/ws/target/release/deps/202406-abc"}
+{"reason":"build-finished","success":true}
+"#;
+
+    #[test]
+    fn parses_messages() {
+        assert_eq!(
+            errors(MESSAGES),
+            ["2024/06.rs:14:7: error[E0308]: mismatched types"]
+        );
+        assert_eq!(
+            executable(MESSAGES, true),
+            Some(PathBuf::from("/ws/target/release/deps/202406-abc"))
+        );
+        assert_eq!(executable(MESSAGES, false), None);
+    }
+
+    #[test]
+    fn diffs_answers() {
+        let answers = |a: &[(u32, &str)]| -> BTreeMap<u32, String> {
+            a.iter().map(|&(p, a)| (p, a.to_owned())).collect()
+        };
+        assert_eq!(
+            diff(None, &answers(&[(1, "41")])),
+            [("Answer", "part 1: 41".to_owned())]
+        );
+        assert_eq!(
+            diff(
+                Some(&answers(&[(1, "41"), (2, "6")])),
+                &answers(&[(1, "41"), (2, "7")])
+            ),
+            [
+                ("Unchanged", "part 1: 41".to_owned()),
+                ("Changed", "part 2: 7, was 6".to_owned())
+            ]
+        );
+        assert_eq!(
+            diff(Some(&answers(&[(2, "6")])), &answers(&[])),
+            [("Missing", "part 2, was 6".to_owned())]
+        );
+    }
+}
@@ -0,0 +1,43 @@
+{ if title }
+//! { title }
+//!
+//! <{ url }>
+
+{ end }
+use advent::prelude::*;
+
+use crate::parse::ParseError;
+
+type Input = Vec<i64>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    todo!("parsing")
+}
+
+fn part1(input: Input) -> i64 {
+    todo!("part 1")
+}
+
+fn part2(input: Input) -> i64 {
+    todo!("part 2")
+}
+
+solution!({ year } / { day }, part1, part2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ignore]
+    #[test]
+    fn default() {
+        let input = default_input().unwrap();
+        assert_eq!(part1(input.clone()), 1);
+        assert_eq!(part2(input), 2);
+    }
+
+    #[test]
+    fn examples() {
+{ examples }
+    }
+}
@@ -0,0 +1,57 @@
+{ if title }
+//! { title }
+//!
+//! <{ url }>
+
+{ end }
+use advent::prelude::*;
+
+use crate::parse::{ParseError, Source};
+
+type Input = HashMap<String, Vec<String>>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    let mut graph: Input = HashMap::new();
+    for line in input.lines() {
+        let mut nodes = source.fields(line, line.split('-'));
+        let (from, to) = (nodes.field()?, nodes.field()?);
+        graph
+            .entry(from.to_owned())
+            .or_default()
+            .push(to.to_owned());
+        graph
+            .entry(to.to_owned())
+            .or_default()
+            .push(from.to_owned());
+    }
+    Ok(graph)
+}
+
+fn part1(input: Input) -> i64 {
+    todo!("part 1")
+}
+
+fn part2(input: Input) -> i64 {
+    todo!("part 2")
+}
+
+solution!({ year } / { day }, part1, part2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ignore]
+    #[test]
+    fn default() {
+        let input = default_input().unwrap();
+        assert_eq!(part1(input.clone()), 1);
+        assert_eq!(part2(input), 2);
+    }
+
+    #[test]
+    fn examples() {
+{ examples }
+    }
+}
@@ -0,0 +1,85 @@
+{ if title }
+//! { title }
+//!
+//! <{ url }>
+
+{ end }
+use advent::prelude::*;
+
+use crate::parse::{ParseError, Source};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Nop,
+}
+
+type Input = Vec<Instruction>;
+
+fn parse_instruction(source: Source<'_>, line: &str) -> Result<Instruction, ParseError> {
+    let mut words = source.fields(line, line.split_whitespace());
+    match words.field()? {
+        "nop" => Ok(Instruction::Nop),
+        op => Err(source.error(op, "unknown instruction")),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    input
+        .lines()
+        .map(|line| parse_instruction(source, line))
+        .collect()
+}
+
+#[derive(Debug, Clone, Default)]
+struct Machine {
+    ip: usize,
+    registers: [i64; 4],
+}
+
+impl Machine {
+    /// Executes one instruction, returning `false` once the program has
+    /// halted.
+    fn step(&mut self, program: &[Instruction]) -> bool {
+        let Some(&instruction) = program.get(self.ip) else {
+            return false;
+        };
+        match instruction {
+            Instruction::Nop => {}
+        }
+        self.ip += 1;
+        true
+    }
+
+    fn run(&mut self, program: &[Instruction]) {
+        while self.step(program) {}
+    }
+}
+
+fn part1(input: Input) -> i64 {
+    todo!("part 1")
+}
+
+fn part2(input: Input) -> i64 {
+    todo!("part 2")
+}
+
+solution!({ year } / { day }, part1, part2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ignore]
+    #[test]
+    fn default() {
+        let input = default_input().unwrap();
+        assert_eq!(part1(input.clone()), 1);
+        assert_eq!(part2(input), 2);
+    }
+
+    #[test]
+    fn examples() {
+{ examples }
+    }
+}
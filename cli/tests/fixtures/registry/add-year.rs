@@ -0,0 +1,21 @@
+//! The solutions.
+
+macro_rules! solutions {
+    ($($tt:tt)*) => {};
+}
+
+// `new` and `remove` edit this list, keep one day per line.
+solutions! {
+    y2023 = "../2023" {
+        d05 = "05.rs",
+    }
+    y2024 = "../2024" {
+        d01 = "01.rs",
+        d03 = "03.rs",
+    }
+    y2025 = "../2025" {
+        d01 = "01.rs",
+    }
+}
+
+pub fn registry() {}
@@ -0,0 +1,66 @@
+//! Round trips inputs through the encrypted store.
+
+mod stub;
+
+use stub::{Stub, Workspace, output};
+
+const NOW: &str = "2024-12-01T05:00:00Z";
+
+#[test]
+fn lock_unlock() {
+    let ws = Workspace::new("inputs");
+    let server = Stub::new()
+        .get("/2024/day/1/input", "3   4\n4   3\n")
+        .start();
+    let run = |args: &[&str]| {
+        output(
+            ws.command(&server, NOW)
+                .env("ADVENT_INPUT_KEY", "hunter2")
+                .args(args),
+        )
+    };
+
+    assert!(run(&["-y", "2024", "fetch"]).status.success());
+    let locked = run(&["inputs", "lock"]);
+    assert!(locked.status.success());
+    assert!(
+        String::from_utf8_lossy(&locked.stdout).contains("Locked 2 of 2 files into `inputs.enc`")
+    );
+    let encrypted = std::fs::read(ws.dir.join("inputs.enc/2024/01.txt.enc")).unwrap();
+    assert!(!String::from_utf8_lossy(&encrypted).contains("3   4"));
+
+    // A fresh clone decrypts on demand instead of downloading
+    std::fs::remove_dir_all(ws.dir.join("input")).unwrap();
+    let fetched = run(&["-y", "2024", "fetch"]);
+    assert!(fetched.status.success());
+    assert!(
+        String::from_utf8_lossy(&fetched.stdout)
+            .contains("Decrypted puzzle input `input/2024/01.txt`")
+    );
+    assert_eq!(ws.read("input/2024/01.txt"), "3   4\n4   3\n");
+    assert_eq!(server.requests().len(), 1);
+
+    // Locking again leaves the store untouched
+    let relocked = run(&["inputs", "lock"]);
+    assert!(String::from_utf8_lossy(&relocked.stdout).contains("Locked 0 of 2 files"));
+    assert_eq!(
+        std::fs::read(ws.dir.join("inputs.enc/2024/01.txt.enc")).unwrap(),
+        encrypted
+    );
+
+    std::fs::remove_dir_all(ws.dir.join("input")).unwrap();
+    let unlocked = run(&["inputs", "unlock"]);
+    assert!(
+        String::from_utf8_lossy(&unlocked.stdout).contains("Unlocked 2 of 2 files into `input`")
+    );
+    assert_eq!(ws.read("input/2024/01.txt"), "3   4\n4   3\n");
+
+    std::fs::remove_dir_all(ws.dir.join("input")).unwrap();
+    let wrong = output(
+        ws.command(&server, NOW)
+            .env("ADVENT_INPUT_KEY", "hunter3")
+            .args(["inputs", "unlock"]),
+    );
+    assert!(!wrong.status.success());
+    assert!(String::from_utf8_lossy(&wrong.stderr).contains("wrong key"));
+}
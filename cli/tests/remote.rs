@@ -0,0 +1,514 @@
+//! Runs the commands that talk to Advent of Code against a local stub.
+
+mod stub;
+
+use std::time::{Duration, Instant};
+
+use stub::{Stub, Workspace, fixture, output};
+
+const RELEASED: &str = "2024-12-06T05:00:00Z";
+const UNRELEASED: &str = "2024-12-06T04:59:59Z";
+
+fn stdout(output: &std::process::Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn new_downloads_input_and_examples() {
+    let ws = Workspace::new("new");
+    let server = Stub::new().day(2024, 6, "part1").start();
+
+    let output = ws.run(&server, RELEASED, &["-y", "2024", "-d", "6", "new"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Downloaded puzzle input `input/2024/06.txt`"));
+
+    assert_eq!(ws.read("input/2024/06.txt"), fixture("2024/06-input.txt"));
+    assert_eq!(
+        ws.read("examples/2024/06/1.txt"),
+        "....#.....\n....^....#\n..........\n"
+    );
+    let source = ws.read("2024/06.rs");
+    assert!(source.contains("solution!(2024 / 06, part1, part2);"));
+    assert!(source.contains("assert_eq!(part1(input), 41);"));
+    assert!(ws.read("src/lib.rs").contains("        d06 = \"06.rs\",\n"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    for request in &requests {
+        assert_eq!(request.header("Cookie"), Some("session=53551011"));
+        assert_eq!(request.header("User-Agent"), Some("advent-cli tests"));
+    }
+}
+
+#[test]
+fn new_from_templates() {
+    let ws = Workspace::new("templates");
+    let server = Stub::new()
+        .day(2024, 6, "part1")
+        .get("/2024/day/5", fixture("2024/06-part1.html"))
+        .get("/2024/day/5/input", fixture("2024/06-input.txt"))
+        .start();
+
+    let output = ws.run(
+        &server,
+        RELEASED,
+        &["-y", "2024", "-d", "6", "new", "--template", "grid"],
+    );
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("from template `grid`"));
+    let source = ws.read("2024/06.rs");
+    assert!(source.starts_with(&format!(
+        "//! Day 6: Guard Gallivant\n//!\n//! <{}/2024/day/6>\n\nuse advent::prelude::*;\n",
+        server.url()
+    )));
+    assert!(source.contains("type Input = Grid<char>;"));
+
+    ws.write(
+        "advent.toml",
+        "template = \"mine\"\n\n[templates]\nmine = \"templates/mine.rs\"\n",
+    );
+    ws.write(
+        "templates/mine.rs",
+        "// { title }\nconst EXAMPLE: &str = r\"{ example }\";\n\
+         { if part2_answer }\nconst PART2: i64 = { part2_answer };\n{ else }\n\
+         const PART1: i64 = { part1_answer };\n{ end }\n",
+    );
+    let output = ws.run(&server, RELEASED, &["-y", "2024", "-d", "5", "new"]);
+    assert!(output.status.success());
+    assert_eq!(
+        ws.read("2024/05.rs"),
+        "// Day 6: Guard Gallivant\n\
+         const EXAMPLE: &str = r\"....#.....\n....^....#\n..........\n\";\n\
+         const PART1: i64 = 41;\n"
+    );
+
+    let output = ws.run(
+        &server,
+        RELEASED,
+        &["-y", "2024", "-d", "8", "new", "--template", "nope"],
+    );
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("expected one of: default, graph, grid, mine, vm")
+    );
+    assert_eq!(server.hits("/2024/day/8"), 0);
+}
+
+#[test]
+fn new_before_release() {
+    let ws = Workspace::new("unreleased");
+    let server = Stub::new().day(2024, 6, "part1").start();
+
+    let output = ws.run(&server, UNRELEASED, &["-y", "2024", "-d", "6", "new"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Unavailable puzzle input"));
+    assert!(ws.read("2024/06.rs").contains("parse_input(\"\").unwrap()"));
+    assert!(!ws.dir.join("input/2024/06.txt").exists());
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn wait_until_unlocked() {
+    let ws = Workspace::new("wait");
+    let server = Stub::new()
+        .route(
+            "GET",
+            "/2024/day/6/input",
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        )
+        .day(2024, 6, "part1")
+        .start();
+
+    // The clock starts at the given time and then advances normally.
+    let started = Instant::now();
+    let output = ws.run(
+        &server,
+        "2024-12-06T04:59:59.5Z",
+        &["-y", "2024", "-d", "6", "wait"],
+    );
+    assert!(output.status.success());
+    assert!(started.elapsed() >= Duration::from_millis(500));
+    let out = stdout(&output);
+    assert!(out.contains("Waiting for 2024/06, unlocks at 2024-12-06 00:00 UTC-05:00 in 1s"));
+    assert!(out.contains("Retrying the puzzle input is not available yet"));
+    assert!(out.contains("Downloaded puzzle input `input/2024/06.txt`"));
+    assert!(out.contains("Downloaded puzzle description `puzzles/2024/06.md`"));
+    assert_eq!(ws.read("input/2024/06.txt"), fixture("2024/06-input.txt"));
+    assert_eq!(server.hits("/2024/day/6/input"), 2);
+    assert_eq!(server.hits("/2024/day/6"), 1);
+}
+
+#[test]
+fn calendar() {
+    let ws = Workspace::new("calendar");
+    let server = Stub::new().start();
+
+    let output = ws.run(&server, RELEASED, &["-y", "2025", "-d", "13", "new"]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("2025 has puzzles on days 1 to 12, not on day 13")
+    );
+    assert!(!ws.dir.join("2025/13.rs").exists());
+
+    let output = ws.run(&server, UNRELEASED, &["-y", "2024", "-d", "6", "fetch"]);
+    assert!(output.status.success());
+    assert!(
+        stdout(&output).is_empty(),
+        "unreleased days are skipped by fetch"
+    );
+
+    let output = ws.run(&server, UNRELEASED, &["-y", "2024", "-d", "6", "new"]);
+    assert!(
+        stdout(&output).contains("Unavailable puzzle input (year: 2024, day: 06) unlocks in 1s")
+    );
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn read_caches_until_both_parts() {
+    let ws = Workspace::new("read");
+    let server = Stub::new()
+        .get("/2024/day/6", fixture("2024/06-part1.html"))
+        .get("/2024/day/6", fixture("2024/06-part2.html"))
+        .start();
+    let expected = |part| {
+        fixture(&format!("2024/06-{part}.md")).replace("https://adventofcode.com", server.url())
+    };
+
+    for part in ["part1", "part2", "part2"] {
+        let output = ws.run(&server, RELEASED, &["-y", "2024", "-d", "6", "read"]);
+        assert!(output.status.success());
+        assert!(stdout(&output).ends_with(&expected(part)));
+    }
+    assert_eq!(ws.read("puzzles/2024/06.md"), expected("part2"));
+    assert_eq!(server.hits("/2024/day/6"), 2);
+}
+
+#[test]
+fn submit_records_verdicts() {
+    let ws = Workspace::new("submit");
+    let server = Stub::new()
+        .answer(2024, 6, "too-high")
+        .answer(2024, 6, "correct")
+        .start();
+    let submit = |answer| {
+        ws.run(
+            &server,
+            RELEASED,
+            &["-y", "2024", "-d", "6", "submit", "1", answer],
+        )
+    };
+
+    assert!(stdout(&submit("5000")).contains("Wrong `5000` is too high"));
+    assert!(stdout(&submit("5001")).contains("Skipped `5001` is known to be wrong (too high)"));
+    assert!(stdout(&submit("4939")).contains("Correct `4939` for part 1"));
+    assert!(stdout(&submit("4939")).contains("`4939` was already accepted"));
+
+    let requests = server.requests();
+    let forms: Vec<_> = requests.iter().map(|r| r.body.as_str()).collect();
+    assert_eq!(forms, ["level=1&answer=5000", "level=1&answer=4939"]);
+    assert_eq!(
+        ws.read("answers.toml"),
+        "[2024.06.part1]\n\
+         answer = \"4939\"\n\n\
+         [[2024.06.part1.rejected]]\n\
+         answer = \"5000\"\n\
+         reason = \"too-high\"\n"
+    );
+}
+
+#[test]
+fn missing_puzzle() {
+    let ws = Workspace::new("missing");
+    let server = Stub::new().start();
+
+    let output = ws.run(&server, RELEASED, &["-y", "2024", "-d", "7", "read"]);
+    assert!(!output.status.success());
+    assert_eq!(server.hits("/2024/day/7"), 1);
+    assert!(!ws.dir.join("puzzles/2024/07.md").exists());
+}
+
+#[test]
+fn config_overrides() {
+    let ws = Workspace::new("config");
+    let server = Stub::new().day(2024, 6, "part1").start();
+    ws.write(
+        "advent.toml",
+        "year = 2024\ninput-dir = \"inputs\"\nuser-agent = \"workspace agent\"\n",
+    );
+    ws.write(
+        ".config/advent/config.toml",
+        "session-command = \"echo from-command\"\nuser-agent = \"user agent\"\nyear = 2023\n",
+    );
+
+    let output = output(
+        ws.command(&server, RELEASED)
+            .env_remove("ADVENT_SESSION")
+            .env_remove("ADVENT_USER_AGENT")
+            .args(["-d", "6", "new"]),
+    );
+    assert!(output.status.success());
+    assert_eq!(ws.read("inputs/2024/06.txt"), fixture("2024/06-input.txt"));
+    for request in server.requests() {
+        assert_eq!(request.header("Cookie"), Some("session=from-command"));
+        assert_eq!(request.header("User-Agent"), Some("workspace agent"));
+    }
+}
+
+#[test]
+fn auth_check() {
+    let ws = Workspace::new("auth");
+    let server = Stub::new()
+        .get("/settings", fixture("settings.html"))
+        .get("/settings", fixture("login.html"))
+        .start();
+
+    let output = ws.run(&server, RELEASED, &["auth", "check"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Logged in as `octocat`"));
+
+    let output = ws.run(&server, RELEASED, &["auth", "check"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("session cookie is invalid"));
+}
+
+#[test]
+fn logged_out_input_is_not_written() {
+    let ws = Workspace::new("logged-out");
+    let server = Stub::new()
+        .get("/2024/day/6", fixture("2024/06-part1.html"))
+        .route(
+            "GET",
+            "/2024/day/6/input",
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )
+        .start();
+
+    let output = ws.run(&server, RELEASED, &["-y", "2024", "-d", "6", "new"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not logged in"));
+    assert!(!ws.dir.join("input/2024/06.txt").exists());
+}
+
+#[test]
+fn profiles() {
+    let ws = Workspace::new("profiles");
+    let server = Stub::new()
+        .day(2024, 6, "part1")
+        .get("/settings", fixture("settings.html"))
+        .start();
+    ws.write(
+        ".config/advent/config.toml",
+        "[profiles.alice]\nsession = \"a11ce\"\n\n[profiles.bob]\nsession-command = \"exit 1\"\n",
+    );
+
+    let output = ws.run(
+        &server,
+        RELEASED,
+        &["-p", "alice", "-y", "2024", "-d", "6", "new"],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        ws.read("input/alice/2024/06.txt"),
+        fixture("2024/06-input.txt")
+    );
+    assert!(!ws.dir.join("input/2024/06.txt").exists());
+
+    let output = ws.run(&server, RELEASED, &["auth", "check", "--all"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("Logged in profile `alice` as `octocat`"));
+    assert!(stdout(&output).contains("Failed profile `bob`"));
+
+    let cookies: Vec<_> = server
+        .requests()
+        .iter()
+        .filter_map(|r| r.header("Cookie").map(str::to_owned))
+        .collect();
+    assert!(cookies.iter().all(|c| c == "session=a11ce"));
+}
+
+#[test]
+fn conditional_requests() {
+    let ws = Workspace::new("etag");
+    let page = fixture("2024/06-part2.html");
+    let server = Stub::new().tagged("/2024/day/6", page, "\"v1\"").start();
+
+    for _ in 0..2 {
+        let output = ws.run(
+            &server,
+            RELEASED,
+            &["-y", "2024", "-d", "6", "read", "--refresh"],
+        );
+        assert!(output.status.success());
+    }
+    assert_eq!(
+        ws.read("puzzles/2024/06.md"),
+        fixture("2024/06-part2.md").replace("https://adventofcode.com", server.url())
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].header("If-None-Match"), None);
+    assert_eq!(requests[1].header("If-None-Match"), Some("\"v1\""));
+    assert_eq!(
+        ws.read("requests.log"),
+        format!(
+            "GET {0}/2024/day/6 200\nGET {0}/2024/day/6 304\n",
+            server.url()
+        )
+    );
+}
+
+#[test]
+fn retries_server_errors() {
+    let ws = Workspace::new("retry");
+    let server = Stub::new()
+        .route("GET", "/settings", 503, "")
+        .get("/settings", fixture("settings.html"))
+        .start();
+
+    let output = ws.run(&server, RELEASED, &["auth", "check"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Retrying"));
+    assert_eq!(server.hits("/settings"), 2);
+}
+
+#[test]
+fn not_unlocked() {
+    let ws = Workspace::new("locked");
+    let server = Stub::new()
+        .route("GET", "/2024/day/6", 404, "404 Not Found")
+        .start();
+
+    let output = ws.run(&server, RELEASED, &["-y", "2024", "-d", "6", "read"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is not unlocked yet"));
+}
+
+#[test]
+fn minimum_interval() {
+    let ws = Workspace::new("interval");
+    let server = Stub::new()
+        .get("/settings", fixture("settings.html"))
+        .start();
+
+    let start = std::time::Instant::now();
+    for _ in 0..2 {
+        let output = output(
+            ws.command(&server, RELEASED)
+                .env("ADVENT_REQUEST_INTERVAL", "0.5")
+                .args(["auth", "check"]),
+        );
+        assert!(output.status.success());
+    }
+    assert!(start.elapsed() >= std::time::Duration::from_millis(500));
+    assert_eq!(ws.read("requests.log").lines().count(), 2);
+}
+
+#[test]
+fn fetch_year() {
+    let ws = Workspace::new("fetch");
+    let server = Stub::new()
+        .get("/2024/day/1/input", "3   4\r\n4   3\r\n")
+        .get("/2024/day/2/input", "7 6 4 2 1")
+        .get("/2024/day/3/input", "mul(2,4)\n")
+        .start();
+    let now = "2024-12-03T05:00:00Z";
+
+    let output = ws.run(&server, now, &["-y", "2024", "fetch"]);
+    assert!(output.status.success());
+    let normalized = |path, fix| format!("Normalized puzzle input `{path}`: {fix}");
+    let out = stdout(&output);
+    assert!(out.contains(&normalized(
+        "input/2024/01.txt",
+        "converted Windows line endings"
+    )));
+    assert!(out.contains(&normalized(
+        "input/2024/02.txt",
+        "added a missing trailing newline"
+    )));
+    assert_eq!(ws.read("input/2024/01.txt"), "3   4\n4   3\n");
+    assert_eq!(ws.read("input/2024/02.txt"), "7 6 4 2 1\n");
+    let meta = ws.read("input/2024/03.meta.toml");
+    assert!(meta.contains("downloaded = \"2024-12-03T05:00:00Z\""));
+    assert!(meta.contains("length = 9"));
+    assert_eq!(server.requests().len(), 3);
+
+    let output = ws.run(&server, now, &["-y", "2024", "fetch"]);
+    assert!(output.status.success());
+    assert_eq!(server.requests().len(), 3);
+
+    ws.write("input/2024/03.txt", "mul(2,4");
+    let output = ws.run(&server, now, &["-y", "2024", "fetch"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("`input/2024/03.txt` does not match its metadata"));
+}
+
+#[test]
+fn leaderboard_is_cached() {
+    let ws = Workspace::new("leaderboard");
+    let path = "/2024/leaderboard/private/view/42.json";
+    let server = Stub::new()
+        .get(path, fixture("2024/leaderboard.json"))
+        .start();
+    let args = ["-y", "2024", "leaderboard", "42"];
+
+    let output = ws.run(&server, "2024-12-02T12:00:00Z", &args);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("Downloaded leaderboard `42` of 2024"));
+    assert!(out.contains("  1)     16     4  **.......................  alice"));
+    assert!(out.contains("bob      25:00:00           -           -"));
+
+    let output = ws.run(&server, "2024-12-02T12:10:00Z", &args);
+    assert!(
+        stdout(&output).contains("Cached leaderboard from 10 minutes ago, refreshing after 5m 00s")
+    );
+    assert_eq!(server.hits(path), 1);
+
+    let output = ws.run(
+        &server,
+        "2024-12-02T12:16:00Z",
+        &["-y", "2024", "-d", "1", "leaderboard", "42"],
+    );
+    let out = stdout(&output);
+    assert!(out.contains("Downloaded leaderboard"));
+    assert!(out.contains("(anonymous user #3)    02:00:00           -           -"));
+    assert_eq!(server.hits(path), 2);
+}
+
+#[test]
+fn status() {
+    let ws = Workspace::new("status");
+    ws.write("input/2024/01.txt", "3   4\n");
+    ws.write(
+        "answers.toml",
+        "[2024.01.part1]\nanswer = \"11\"\n\n[2024.01.part2]\nanswer = \"31\"\n\n\
+         [2024.02.part1]\nanswer = \"2\"\n",
+    );
+    let server = Stub::new()
+        .get("/2024", fixture("2024/calendar.html"))
+        .start();
+
+    let output = ws.run(&server, "2024-12-03T05:00:00Z", &["-y", "2024", "status"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains(
+        "\
+Day  Stars  Solution  Input    Test  Answers
+  1  **     yes       yes      -     2/2
+  2  *      -         -        -     1/2
+  3         -         -        -     0/2
+"
+    ));
+    assert!(out.contains("Stars 3 of 50 in 2024"));
+    assert!(out.contains("Unfinished 2024 days 2, 3"));
+
+    let output = ws.run(&server, "2024-11-30T05:00:00Z", &["-y", "2024", "status"]);
+    assert!(stdout(&output).contains("Unavailable 2024 starts in 1d 00h 00m 00s"));
+    assert_eq!(server.hits("/2024"), 1);
+}
@@ -0,0 +1,290 @@
+//! A stub Advent of Code server for running the CLI offline.
+//!
+//! Responses are registered per method and path and served in order, the
+//! last one is repeated. Every request is recorded so tests can check what
+//! the CLI sent.
+
+#![allow(dead_code)]
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A request received by the stub.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+    /// Sent as the `ETag`, a matching `If-None-Match` gets a 304.
+    pub etag: Option<String>,
+}
+
+type Routes = BTreeMap<(String, String), VecDeque<Response>>;
+
+#[derive(Debug, Default)]
+pub struct Stub {
+    routes: Routes,
+}
+
+/// A running stub server.
+#[derive(Debug)]
+pub struct Server {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+pub fn fixture(path: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(path);
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {err}", path.display()))
+}
+
+impl Stub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn route(mut self, method: &str, path: &str, status: u16, body: impl Into<String>) -> Self {
+        self.routes
+            .entry((method.into(), path.into()))
+            .or_default()
+            .push_back(Response {
+                status,
+                body: body.into(),
+                etag: None,
+            });
+        self
+    }
+
+    /// Serves a page with an `ETag`, for testing conditional requests.
+    pub fn tagged(mut self, path: &str, body: impl Into<String>, etag: &str) -> Self {
+        self.routes
+            .entry(("GET".into(), path.into()))
+            .or_default()
+            .push_back(Response {
+                status: 200,
+                body: body.into(),
+                etag: Some(etag.into()),
+            });
+        self
+    }
+
+    pub fn get(self, path: &str, body: impl Into<String>) -> Self {
+        self.route("GET", path, 200, body)
+    }
+
+    pub fn post(self, path: &str, body: impl Into<String>) -> Self {
+        self.route("POST", path, 200, body)
+    }
+
+    /// Serves the puzzle page and input of a day from the fixtures.
+    pub fn day(self, year: u32, day: u32, page: &str) -> Self {
+        self.get(
+            &format!("/{year}/day/{day}"),
+            fixture(&format!("{year}/{day:02}-{page}.html")),
+        )
+        .get(
+            &format!("/{year}/day/{day}/input"),
+            fixture(&format!("{year}/{day:02}-input.txt")),
+        )
+    }
+
+    /// Serves an answer response for a day from the fixtures.
+    pub fn answer(self, year: u32, day: u32, verdict: &str) -> Self {
+        self.post(
+            &format!("/{year}/day/{day}/answer"),
+            fixture(&format!("{year}/{day:02}-answer-{verdict}.html")),
+        )
+    }
+
+    pub fn start(self) -> Server {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let mut routes = self.routes;
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                if let Err(err) = handle(stream, &mut routes, &recorded) {
+                    eprintln!("stub: {err}");
+                }
+            }
+        });
+        Server { url, requests }
+    }
+}
+
+fn handle(
+    stream: TcpStream,
+    routes: &mut Routes,
+    requests: &Mutex<Vec<Request>>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_owned(), value.trim().to_owned()));
+        }
+    }
+    let length = headers
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    let key = (method.clone(), path.clone());
+    let if_none_match = headers
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case("if-none-match"))
+        .map(|(_, v)| v.clone());
+    requests.lock().unwrap().push(Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    });
+
+    let response = match routes.get_mut(&key) {
+        Some(queue) if queue.len() > 1 => queue.pop_front().unwrap(),
+        Some(queue) => queue[0].clone(),
+        None => Response {
+            status: 404,
+            body: "404 Not Found".into(),
+            etag: None,
+        },
+    };
+    let (status, body, etag) = match response.etag {
+        Some(etag) if if_none_match.as_ref() == Some(&etag) => (304, String::new(), Some(etag)),
+        etag => (response.status, response.body, etag),
+    };
+    let etag = etag.map(|e| format!("ETag: {e}\r\n")).unwrap_or_default();
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status} Stub\r\n{etag}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len(),
+    )?;
+    stream.flush()
+}
+
+impl Server {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Returns the number of requests made for the given path.
+    pub fn hits(&self, path: &str) -> usize {
+        self.requests().iter().filter(|r| r.path == path).count()
+    }
+}
+
+/// A temporary workspace to run the CLI in.
+#[derive(Debug)]
+pub struct Workspace {
+    pub dir: PathBuf,
+}
+
+impl Workspace {
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("advent-cli-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"advent-bins\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("src/lib.rs"),
+            "solutions! {\n    y2024 = \"../2024\" {\n        d01 = \"01.rs\",\n    }\n}\n",
+        )
+        .unwrap();
+        Self { dir }
+    }
+
+    pub fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.dir.join(path)).unwrap()
+    }
+
+    pub fn write(&self, path: &str, contents: &str) {
+        let path = self.dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    /// Returns a command running the CLI against the stub server at the given
+    /// time, isolated from the user's config.
+    pub fn command(&self, server: &Server, now: &str) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_advent-cli"));
+        command
+            .env("CARGO_WORKSPACE_DIR", &self.dir)
+            .env("XDG_CONFIG_HOME", self.dir.join(".config"))
+            .env("ADVENT_BASE_URL", server.url())
+            .env("ADVENT_SESSION", "53551011")
+            .env("ADVENT_USER_AGENT", "advent-cli tests")
+            .env("ADVENT_NOW", now)
+            .env("ADVENT_REQUEST_INTERVAL", "0")
+            .env("ADVENT_REQUEST_LOG", self.dir.join("requests.log"))
+            .env_remove("CARGO_TARGET_DIR");
+        command
+    }
+
+    /// Runs the CLI against the stub server at the given time.
+    pub fn run(&self, server: &Server, now: &str, args: &[&str]) -> Output {
+        output(self.command(server, now).args(args))
+    }
+}
+
+/// Runs a command, passing its stderr through to the test output.
+pub fn output(command: &mut Command) -> Output {
+    let output = command.output().unwrap();
+    eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
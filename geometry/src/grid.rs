@@ -0,0 +1,298 @@
+//! Rectangular grids of cells, indexed by [`Point2D`].
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::Point2D;
+
+/// A rectangular grid, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// An error parsing a grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError<'a, E> {
+    /// A row that is not as wide as the first one.
+    Ragged(&'a str),
+    /// An error converting a cell.
+    Cell(E),
+}
+
+impl<E: Display> Display for GridError<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Ragged(row) => write!(f, "row `{row}` is not as wide as the first one"),
+            GridError::Cell(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for GridError<'_, E> {}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells, row by row.
+    ///
+    /// Panics if there are not `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "cells of a {width}x{height} grid"
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses a grid with a cell per character and a row per line, leaving
+    /// out trailing empty lines.
+    ///
+    /// `cell` converts a character, which it also gets as a slice of the
+    /// input, so that errors can point at it.
+    pub fn parse_with<'a, E>(
+        input: &'a str,
+        mut cell: impl FnMut(char, &'a str) -> Result<T, E>,
+    ) -> Result<Self, GridError<'a, E>> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for row in input.trim_end_matches(['\n', '\r']).lines() {
+            let before = cells.len();
+            for (i, c) in row.char_indices() {
+                let cell = cell(c, &row[i..i + c.len_utf8()]).map_err(GridError::Cell)?;
+                cells.push(cell);
+            }
+            if *width.get_or_insert(cells.len() - before) != cells.len() - before {
+                return Err(GridError::Ragged(row));
+            }
+            height += 1;
+        }
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point2D) -> bool {
+        (0..self.width as i64).contains(&p.x) && (0..self.height as i64).contains(&p.y)
+    }
+
+    fn index_of(&self, p: Point2D) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    /// Returns the cell at the point, or `None` outside the grid.
+    pub fn get(&self, p: Point2D) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point2D) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Returns the first point, row by row, whose cell matches.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point2D> {
+        self.iter()
+            .find_map(|(p, cell)| predicate(cell).then_some(p))
+    }
+
+    /// Returns every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point2D> + use<T> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point2D::new(x, y)))
+    }
+
+    /// Returns every point with its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2D, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} of a grid {} wide", self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Walks from `start` by `step` until leaving the grid, e.g. along a row,
+    /// a column or a diagonal.
+    pub fn ray(&self, start: Point2D, step: Point2D) -> impl Iterator<Item = (Point2D, &T)> {
+        assert_ne!(step, Point2D::ZERO, "a ray needs a direction");
+        let points = std::iter::successors(Some(start), move |&p| Some(p + step));
+        points.map_while(|p| Some((p, self.get(p)?)))
+    }
+
+    /// Returns the orthogonally adjacent points inside the grid.
+    pub fn neighbors4(&self, p: Point2D) -> impl Iterator<Item = Point2D> {
+        p.neighbors4().filter(|&n| self.contains(n))
+    }
+
+    /// Returns the adjacent points inside the grid, including diagonals.
+    pub fn neighbors8(&self, p: Point2D) -> impl Iterator<Item = Point2D> {
+        p.neighbors8().filter(|&n| self.contains(n))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flat_map(|c| c.cloned()).collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| (x, y)))
+            .map(|(x, y)| self.row(y)[x].clone());
+        Self::new(self.height, self.width, cells.collect())
+    }
+
+    /// Rotates the grid by 90° counterclockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width).rev().flat_map(|x| self.column(x).cloned());
+        Self::new(self.height, self.width, cells.collect())
+    }
+}
+
+impl<T> Index<Point2D> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2D) -> &T {
+        match self.get(p) {
+            Some(cell) => cell,
+            None => panic!(
+                "{p:?} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point2D> for Grid<T> {
+    fn index_mut(&mut self, p: Point2D) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(p) {
+            Some(cell) => cell,
+            None => panic!("{p:?} is outside of the {width}x{height} grid"),
+        }
+    }
+}
+
+/// Renders the cells of each row next to each other, a line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                cell.fmt(f)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse_with(input, |c, _| Ok::<_, ()>(c)).unwrap()
+    }
+
+    #[test]
+    fn parses() {
+        let g = grid("ab.\n.cd\n\n");
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g.to_string(), "ab.\n.cd\n");
+        assert_eq!(g.find(|&c| c == 'c'), Some(Point2D::new(1, 1)));
+        assert_eq!(g.find(|&c| c == 'x'), None);
+
+        let input = "ab\nabc\n";
+        let err = Grid::parse_with(input, |c, _| Ok::<_, ()>(c)).unwrap_err();
+        assert_eq!(err, GridError::Ragged("abc"));
+        let err = Grid::parse_with(input, |c, at| if c == 'b' { Err(at) } else { Ok(c) });
+        assert_eq!(err, Err(GridError::Cell("b")));
+        assert_eq!(grid("").width(), 0);
+    }
+
+    #[test]
+    fn access() {
+        let mut g = grid("ab\ncd\n");
+        assert_eq!(g.get(Point2D::new(1, 0)), Some(&'b'));
+        assert_eq!(g.get(Point2D::new(2, 0)), None);
+        assert_eq!(g.get(Point2D::new(0, -1)), None);
+        g[Point2D::new(0, 1)] = 'x';
+        assert_eq!(g.row(1), ['x', 'd']);
+        assert_eq!(g.column(1).collect::<String>(), "bd");
+        assert_eq!(
+            g.neighbors4(Point2D::ZERO).collect::<Vec<_>>(),
+            [Point2D::new(1, 0), Point2D::new(0, 1)]
+        );
+        assert_eq!(g.neighbors8(Point2D::ZERO).count(), 3);
+    }
+
+    #[test]
+    fn rays() {
+        let g = grid("abc\ndef\nghi\n");
+        let ray = |x, y, dx, dy| -> String {
+            g.ray(Point2D::new(x, y), Point2D::new(dx, dy))
+                .map(|(_, c)| c)
+                .collect()
+        };
+        assert_eq!(ray(0, 0, 1, 1), "aei");
+        assert_eq!(ray(2, 0, -1, 1), "ceg");
+        assert_eq!(ray(1, 2, 0, -1), "heb");
+        assert_eq!(ray(3, 0, 1, 0), "");
+    }
+
+    #[test]
+    fn transforms() {
+        let g = grid("abc\ndef\n");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotate_right().to_string(), "da\neb\nfc\n");
+        assert_eq!(g.rotate_left().to_string(), "cf\nbe\nad\n");
+        assert_eq!(g.rotate_right().rotate_left(), g);
+        assert_eq!(g.map(|c| c.to_ascii_uppercase()).row(0), ['A', 'B', 'C']);
+    }
+}
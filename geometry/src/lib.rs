@@ -0,0 +1,359 @@
+//! 2D and 3D vectors for the solutions: points, offsets and directions on
+//! puzzle grids.
+//!
+//! Grids are indexed by row and column, so the y axis grows downwards:
+//! [`Vec2::UP`] is `(0, -1)` and turning right goes from up to right.
+//! [`Grid`] stores the cells of such a grid.
+
+mod grid;
+
+pub use grid::{Grid, GridError};
+
+use std::iter::Sum;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+/// A point on a grid.
+pub type Point2D = Vec2<i64>;
+
+/// A point in space.
+pub type Point3D = Vec3<i64>;
+
+/// The numbers vectors are made of.
+pub trait Number:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn to_f64(self) -> f64;
+
+    /// Returns the distance between the numbers, which also works for
+    /// unsigned ones.
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+/// Numbers that can be negative, and so make directions.
+pub trait Signed: Number + Neg<Output = Self> {
+    const MINUS_ONE: Self;
+}
+
+macro_rules! numbers {
+    ($($t:ty),*) => {
+        $(
+            impl Number for $t {
+                const ZERO: Self = 0 as $t;
+                const ONE: Self = 1 as $t;
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! signed {
+    ($($t:ty),*) => {
+        $(
+            impl Signed for $t {
+                const MINUS_ONE: Self = -1 as $t;
+            }
+        )*
+    };
+}
+
+numbers!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+signed!(i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Defines a vector type with the given components, its operators and
+/// distances.
+macro_rules! vector {
+    ($(#[$doc:meta])* $name:ident { $($c:ident),+ }, $tuple:ty, $n:literal) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<T> {
+            $(pub $c: T,)+
+        }
+
+        impl<T> $name<T> {
+            pub const fn new($($c: T),+) -> Self {
+                Self { $($c),+ }
+            }
+        }
+
+        impl<T: Number> $name<T> {
+            pub const ZERO: Self = Self { $($c: T::ZERO),+ };
+
+            /// Returns the sum of the distances along each axis.
+            pub fn manhattan(self, other: Self) -> T {
+                T::ZERO $(+ self.$c.abs_diff(other.$c))+
+            }
+
+            /// Returns the largest distance along an axis, the number of
+            /// king moves between the points.
+            pub fn chebyshev(self, other: Self) -> T {
+                let mut max = T::ZERO;
+                $(
+                    let d = self.$c.abs_diff(other.$c);
+                    if d > max {
+                        max = d;
+                    }
+                )+
+                max
+            }
+
+            /// Returns the straight line distance.
+            pub fn euclidean(self, other: Self) -> f64 {
+                (0.0 $(+ self.$c.abs_diff(other.$c).to_f64().powi(2))+).sqrt()
+            }
+
+            pub fn dot(self, other: Self) -> T {
+                T::ZERO $(+ self.$c * other.$c)+
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($c: self.$c + rhs.$c),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($c: self.$c - rhs.$c),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($c: -self.$c),+ }
+            }
+        }
+
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                Self { $($c: self.$c * rhs),+ }
+            }
+        }
+
+        impl<T: Div<Output = T> + Copy> Div<T> for $name<T> {
+            type Output = Self;
+
+            fn div(self, rhs: T) -> Self {
+                Self { $($c: self.$c / rhs),+ }
+            }
+        }
+
+        /// The remainder of each component.
+        impl<T: Rem<Output = T> + Copy> Rem<T> for $name<T> {
+            type Output = Self;
+
+            fn rem(self, rhs: T) -> Self {
+                Self { $($c: self.$c % rhs),+ }
+            }
+        }
+
+        /// The remainder of each component by the one of the other vector,
+        /// e.g. to wrap a point around the size of a grid.
+        impl<T: Rem<Output = T>> Rem for $name<T> {
+            type Output = Self;
+
+            fn rem(self, rhs: Self) -> Self {
+                Self { $($c: self.$c % rhs.$c),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $name<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$c += rhs.$c;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $name<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$c -= rhs.$c;)+
+            }
+        }
+
+        impl<T: MulAssign + Copy> MulAssign<T> for $name<T> {
+            fn mul_assign(&mut self, rhs: T) {
+                $(self.$c *= rhs;)+
+            }
+        }
+
+        impl<T: DivAssign + Copy> DivAssign<T> for $name<T> {
+            fn div_assign(&mut self, rhs: T) {
+                $(self.$c /= rhs;)+
+            }
+        }
+
+        impl<T: RemAssign> RemAssign for $name<T> {
+            fn rem_assign(&mut self, rhs: Self) {
+                $(self.$c %= rhs.$c;)+
+            }
+        }
+
+        impl<T: Number> Sum for $name<T> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |sum, v| sum + v)
+            }
+        }
+
+        impl<T> From<$tuple> for $name<T> {
+            fn from(($($c),+): $tuple) -> Self {
+                Self { $($c),+ }
+            }
+        }
+
+        impl<T> From<$name<T>> for $tuple {
+            fn from(v: $name<T>) -> Self {
+                ($(v.$c),+)
+            }
+        }
+
+        impl<T> From<[T; $n]> for $name<T> {
+            fn from([$($c),+]: [T; $n]) -> Self {
+                Self { $($c),+ }
+            }
+        }
+    };
+}
+
+vector!(
+    /// A 2D vector, e.g. a point on a grid or an offset between two.
+    Vec2 { x, y },
+    (T, T),
+    2
+);
+
+vector!(
+    /// A 3D vector.
+    Vec3 { x, y, z },
+    (T, T, T),
+    3
+);
+
+impl<T: Signed> Vec2<T> {
+    pub const UP: Self = Self::new(T::ZERO, T::MINUS_ONE);
+    pub const RIGHT: Self = Self::new(T::ONE, T::ZERO);
+    pub const DOWN: Self = Self::new(T::ZERO, T::ONE);
+    pub const LEFT: Self = Self::new(T::MINUS_ONE, T::ZERO);
+
+    /// The four directions, clockwise from up.
+    pub const ORTHOGONAL: [Self; 4] = [Self::UP, Self::RIGHT, Self::DOWN, Self::LEFT];
+
+    /// The eight directions including diagonals, clockwise from up.
+    pub const ALL: [Self; 8] = [
+        Self::UP,
+        Self::new(T::ONE, T::MINUS_ONE),
+        Self::RIGHT,
+        Self::new(T::ONE, T::ONE),
+        Self::DOWN,
+        Self::new(T::MINUS_ONE, T::ONE),
+        Self::LEFT,
+        Self::new(T::MINUS_ONE, T::MINUS_ONE),
+    ];
+
+    /// Rotates by 90° clockwise on the grid, e.g. from up to right.
+    pub fn turn_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates by 90° counterclockwise on the grid, e.g. from up to left.
+    pub fn turn_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Returns the four orthogonally adjacent points, clockwise from up.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Self::ORTHOGONAL.into_iter().map(move |d| self + d)
+    }
+
+    /// Returns the eight adjacent points including diagonals, clockwise from
+    /// up.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter().map(move |d| self + d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        let mut p = Point2D::new(3, -4);
+        assert_eq!(p + Point2D::new(1, 1), Point2D::new(4, -3));
+        assert_eq!(p - Point2D::new(1, 1), Point2D::new(2, -5));
+        assert_eq!(-p, Point2D::new(-3, 4));
+        assert_eq!(p * 2, Point2D::new(6, -8));
+        assert_eq!(p / 2, Point2D::new(1, -2));
+        assert_eq!(p % Point2D::new(2, 3), Point2D::new(1, -1));
+        p += Point2D::new(1, 0);
+        p *= 3;
+        assert_eq!(p, Point2D::new(12, -12));
+        assert_eq!(
+            [Point2D::new(1, 2), Point2D::new(3, 4)]
+                .into_iter()
+                .sum::<Point2D>(),
+            Point2D::new(4, 6)
+        );
+        assert_eq!(Point2D::from((1, 2)), Point2D::from([1, 2]));
+        assert_eq!(<(i64, i64)>::from(Point2D::new(1, 2)), (1, 2));
+        assert_eq!(Point3D::new(1, 2, 3) * 2, Point3D::new(2, 4, 6));
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point2D::new(1, 1), Point2D::new(4, -3));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean(b), 5.0);
+        assert_eq!(Vec2::<usize>::new(5, 0).manhattan(Vec2::new(2, 3)), 6);
+        assert_eq!(Point3D::new(0, 0, 0).manhattan(Point3D::new(1, -2, 3)), 6);
+        assert_eq!(Point3D::new(1, 2, 3).dot(Point3D::new(4, 5, 6)), 32);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Point2D::UP.turn_right(), Point2D::RIGHT);
+        assert_eq!(Point2D::RIGHT.turn_right(), Point2D::DOWN);
+        assert_eq!(Point2D::UP.turn_left(), Point2D::LEFT);
+        assert_eq!(
+            Point2D::new(2, 1).turn_left().turn_right(),
+            Point2D::new(2, 1)
+        );
+
+        let p = Point2D::new(5, 5);
+        assert_eq!(
+            p.neighbors4().collect::<Vec<_>>(),
+            [(5, 4), (6, 5), (5, 6), (4, 5)].map(Point2D::from)
+        );
+        assert_eq!(p.neighbors8().count(), 8);
+        assert!(p.neighbors8().all(|n| n.chebyshev(p) == 1));
+    }
+}
@@ -0,0 +1,436 @@
+//! Shortest path searches over any states: breadth-first search, Dijkstra and
+//! A*, with the paths they find.
+//!
+//! States are given by a start and a closure returning the neighbours of a
+//! state, with the cost of the step to each of them for the weighted searches.
+//! A search stops once it has taken a state matching `goal` and every other
+//! goal as cheap, or explores everything it can reach if nothing matches.
+//!
+//! Reached states are found again through a hash map, or with the `_in`
+//! variants through any [`States`], such as [`Dense`] for the cells of a grid.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use rustc_hash::FxHashMap;
+
+/// The costs of paths, with `Default` as zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// Where a search looks up the node of each state it reached.
+pub trait States<S> {
+    fn get(&self, state: &S) -> Option<usize>;
+
+    fn insert(&mut self, state: &S, node: usize);
+}
+
+/// Hashed with the hash of rustc, much faster than the default one on the
+/// small states of puzzles, which need no protection from collision attacks.
+impl<S: Clone + Eq + Hash> States<S> for FxHashMap<S, usize> {
+    fn get(&self, state: &S) -> Option<usize> {
+        FxHashMap::get(self, state).copied()
+    }
+
+    fn insert(&mut self, state: &S, node: usize) {
+        FxHashMap::insert(self, state.clone(), node);
+    }
+}
+
+/// States numbered from zero up to a bound, e.g. the points of a grid row by
+/// row, whose nodes are then looked up in a vector instead of a hash map.
+#[derive(Debug, Clone)]
+pub struct Dense<F> {
+    number: F,
+    nodes: Vec<usize>,
+}
+
+impl<F> Dense<F> {
+    /// Creates the lookup for `len` states, which `number` numbers below it.
+    pub fn new(len: usize, number: F) -> Self {
+        Self {
+            number,
+            nodes: vec![usize::MAX; len],
+        }
+    }
+}
+
+impl<S, F: Fn(&S) -> usize> States<S> for Dense<F> {
+    fn get(&self, state: &S) -> Option<usize> {
+        let node = self.nodes[(self.number)(state)];
+        (node != usize::MAX).then_some(node)
+    }
+
+    fn insert(&mut self, state: &S, node: usize) {
+        self.nodes[(self.number)(state)] = node;
+    }
+}
+
+/// The states reached by a search, with their cheapest costs and the states
+/// leading to them on cheapest paths.
+#[derive(Debug, Clone)]
+pub struct Search<S, C, X = FxHashMap<S, usize>> {
+    nodes: Vec<Node<S, C>>,
+    /// The lists of other parents of nodes, as a parent and the next entry of
+    /// its list, all in one vector to not allocate for each node.
+    other_parents: Vec<(usize, usize)>,
+    states: X,
+    goals: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    /// The state before this one on a cheapest path, or `NONE` for the start.
+    parent: usize,
+    /// The first entry of the list of states before this one on other
+    /// cheapest paths, or `NONE`.
+    other_parents: usize,
+    done: bool,
+}
+
+/// Marks a missing node or entry, as `Option` would make nodes larger.
+const NONE: usize = usize::MAX;
+
+fn some(i: usize) -> Option<usize> {
+    (i != NONE).then_some(i)
+}
+
+/// Searches breadth-first, where every step costs 1.
+pub fn bfs<S, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    bfs_in(FxHashMap::default(), start, neighbors, goal)
+}
+
+/// Searches breadth-first like [`bfs`], looking up reached states in `states`.
+pub fn bfs_in<S, I, X>(
+    states: X,
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize, X>
+where
+    I: IntoIterator<Item = S>,
+    X: States<S>,
+{
+    let mut search = Search::new(states, start, 0);
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        let cost = search.nodes[i].cost;
+        if search.goal_cost().is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+        if goal(&search.nodes[i].state) {
+            search.goals.push(i);
+            continue;
+        }
+
+        for next in neighbors(&search.nodes[i].state) {
+            if let Some(next) = search.relax(next, i, cost + 1) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    search
+}
+
+/// Searches by the cheapest state first. Costs of steps must not be negative.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    dijkstra_in(FxHashMap::default(), start, neighbors, goal)
+}
+
+/// Searches like [`dijkstra`], looking up reached states in `states`.
+pub fn dijkstra_in<S, C, I, X>(
+    states: X,
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Search<S, C, X>
+where
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    X: States<S>,
+{
+    astar_in(states, start, neighbors, |_| C::default(), goal)
+}
+
+/// Searches by the cheapest state first, counting what `heuristic` estimates
+/// is left to reach a goal. The heuristic must never overestimate it.
+pub fn astar<S, C, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_in(FxHashMap::default(), start, neighbors, heuristic, goal)
+}
+
+/// Searches like [`astar`], looking up reached states in `states`.
+pub fn astar_in<S, C, I, X>(
+    states: X,
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Search<S, C, X>
+where
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    X: States<S>,
+{
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+    let mut search = Search::new(states, start, C::default());
+    while let Some(Reverse((estimate, i))) = heap.pop() {
+        if search
+            .goal_cost()
+            .is_some_and(|goal_cost| estimate > goal_cost)
+        {
+            break;
+        }
+        let node = &mut search.nodes[i];
+        if node.done {
+            continue;
+        }
+        node.done = true;
+        if goal(&node.state) {
+            search.goals.push(i);
+            continue;
+        }
+
+        let cost = node.cost;
+        for (next, step) in neighbors(&search.nodes[i].state) {
+            if let Some(next) = search.relax(next, i, cost + step) {
+                let node = &search.nodes[next];
+                heap.push(Reverse((node.cost + heuristic(&node.state), next)));
+            }
+        }
+    }
+
+    search
+}
+
+impl<S, C: Cost, X: States<S>> Search<S, C, X> {
+    fn new(mut states: X, start: S, cost: C) -> Self {
+        states.insert(&start, 0);
+        Self {
+            nodes: vec![Node {
+                state: start,
+                cost,
+                parent: NONE,
+                other_parents: NONE,
+                done: false,
+            }],
+            other_parents: Vec::new(),
+            states,
+            goals: Vec::new(),
+        }
+    }
+
+    /// Records a step from `parent`, returning the stepped to state if it has
+    /// not been reached as cheaply before.
+    fn relax(&mut self, state: S, parent: usize, cost: C) -> Option<usize> {
+        match self.states.get(&state) {
+            None => {
+                let i = self.nodes.len();
+                self.states.insert(&state, i);
+                self.nodes.push(Node {
+                    state,
+                    cost,
+                    parent,
+                    other_parents: NONE,
+                    done: false,
+                });
+                Some(i)
+            }
+            Some(i) => {
+                let node = &mut self.nodes[i];
+                if cost < node.cost {
+                    node.cost = cost;
+                    node.parent = parent;
+                    node.other_parents = NONE;
+                    node.done = false;
+                    Some(i)
+                } else {
+                    if cost == node.cost && self.parents(i).all(|p| p != parent) {
+                        let next = self.nodes[i].other_parents;
+                        self.nodes[i].other_parents = self.other_parents.len();
+                        self.other_parents.push((parent, next));
+                    }
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl<S, C, X> Search<S, C, X> {
+    /// Returns the states before the node on every cheapest path found.
+    fn parents(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        let entries = std::iter::successors(some(self.nodes[node].other_parents), |&e| {
+            some(self.other_parents[e].1)
+        });
+        let others = entries.map(|e| self.other_parents[e].0);
+        some(self.nodes[node].parent).into_iter().chain(others)
+    }
+}
+
+impl<S, C: Copy, X: States<S>> Search<S, C, X> {
+    /// Returns the first goal reached.
+    pub fn goal(&self) -> Option<&S> {
+        self.goals.first().map(|&i| &self.nodes[i].state)
+    }
+
+    /// Returns every goal reached, all as cheap as the first one.
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|&i| &self.nodes[i].state)
+    }
+
+    /// Returns the cost of reaching a goal.
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goals.first().map(|&i| self.nodes[i].cost)
+    }
+
+    /// Returns the cost of the cheapest path found to the state.
+    ///
+    /// Only states cheaper than the goal are sure to have their cheapest
+    /// cost, as the search stops there.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.states.get(state).map(|i| self.nodes[i].cost)
+    }
+
+    /// Returns every state reached with the cost of reaching it.
+    pub fn costs(&self) -> impl Iterator<Item = (&S, C)> {
+        self.nodes.iter().map(|node| (&node.state, node.cost))
+    }
+
+    /// Returns a cheapest path from the start to the state, both included.
+    pub fn path(&self, to: &S) -> Option<Vec<&S>> {
+        let mut i = self.states.get(to)?;
+        let mut path = vec![&self.nodes[i].state];
+        while let Some(parent) = some(self.nodes[i].parent) {
+            path.push(&self.nodes[parent].state);
+            i = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns every state on any cheapest path from the start to any of the
+    /// given states, each once.
+    pub fn on_cheapest_paths<'a>(&'a self, to: impl IntoIterator<Item = &'a S>) -> Vec<&'a S> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack: Vec<usize> = to.into_iter().filter_map(|s| self.states.get(s)).collect();
+        let mut states = Vec::new();
+        while let Some(i) = stack.pop() {
+            if !std::mem::replace(&mut seen[i], true) {
+                states.push(&self.nodes[i].state);
+                stack.extend(self.parents(i));
+            }
+        }
+        states
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A maze with a single way from the start to the goal, and a dead end.
+    const MAZE: &[&str] = &["S..#....", ".#.#.##.", ".#...#..", ".####.#.", "......#G"];
+
+    fn open(x: i32, y: i32) -> bool {
+        let Some(row) = MAZE.get(y as usize) else {
+            return false;
+        };
+        row.as_bytes().get(x as usize).is_some_and(|&c| c != b'#')
+    }
+
+    fn neighbors(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+            .into_iter()
+            .filter(|&(x, y)| open(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn breadth_first() {
+        let search = bfs((0, 0), neighbors, |&p| p == (7, 4));
+        assert_eq!(search.goal(), Some(&(7, 4)));
+        assert_eq!(search.goal_cost(), Some(15));
+        let path = search.path(&(7, 4)).unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!((path[0], path[15]), (&(0, 0), &(7, 4)));
+        assert!(path.windows(2).all(|w| neighbors(w[0]).contains(w[1])));
+
+        let all = bfs((0, 0), neighbors, |_| false);
+        assert_eq!(all.goal(), None);
+        assert_eq!(all.cost(&(4, 4)), Some(8));
+        assert_eq!(all.costs().count(), 27);
+
+        let dense = Dense::new(40, |&(x, y): &(i32, i32)| (y * 8 + x) as usize);
+        let search = bfs_in(dense, (0, 0), neighbors, |&p| p == (7, 4));
+        assert_eq!(search.goal_cost(), Some(15));
+        assert_eq!(search.cost(&(4, 4)), Some(8));
+        assert_eq!(search.cost(&(1, 1)), None);
+        assert_eq!(search.path(&(7, 4)).unwrap().len(), 16);
+    }
+
+    #[test]
+    fn weighted() {
+        // Each of the six steps down costs 10.
+        let weighted = |p: &(i32, i32)| {
+            neighbors(p)
+                .into_iter()
+                .map(|n| (n, if n.1 > p.1 { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let search = dijkstra((0, 0), weighted, |&p| p == (7, 4));
+        assert_eq!(search.goal_cost(), Some(69));
+
+        let manhattan = |&(x, y): &(i32, i32)| (7 - x) + (4 - y);
+        let search = astar((0, 0), weighted, manhattan, |&p| p == (7, 4));
+        assert_eq!(search.goal_cost(), Some(69));
+        assert_eq!(search.path(&(7, 4)).unwrap().len(), 16);
+    }
+
+    #[test]
+    fn all_cheapest_paths() {
+        // Both ways around the square are as short.
+        let right_or_down = |&(x, y): &(i32, i32)| [(x + 1, y), (x, y + 1)];
+        let search = bfs((0, 0), right_or_down, |&p| p == (1, 1));
+        assert_eq!(search.goal_cost(), Some(2));
+        let mut states = search.on_cheapest_paths(search.goals());
+        states.sort();
+        assert_eq!(states, [&(0, 0), &(0, 1), &(1, 0), &(1, 1)]);
+
+        let search = bfs(0, |&n| [n + 1, n + 2], |&n| n == 3 || n == 4);
+        assert_eq!(search.goals().collect::<Vec<_>>(), [&3, &4]);
+        assert_eq!(search.on_cheapest_paths(search.goals()).len(), 5);
+    }
+}
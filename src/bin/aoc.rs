@@ -0,0 +1,119 @@
+//! Runs the registered solutions: `aoc YEAR DAY` for one day, `aoc YEAR` for
+//! every day of a year, or `aoc` for all of them. With `--bench` each day is
+//! run repeatedly and the mean and standard deviation of every step printed.
+//! `--input PATH` runs a single day on another input, `-` for stdin.
+
+use std::env;
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
+
+use advent_bins::parse::ParseError;
+use advent_bins::{Run, Solution};
+
+/// How long to keep benchmarking a day, after at least `MIN_RUNS`.
+const BENCH_FOR: Duration = Duration::from_secs(1);
+const MIN_RUNS: usize = 10;
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let bench = args.iter().any(|a| a == "--bench");
+    args.retain(|a| a != "--bench");
+    let input = match args.iter().position(|a| a == "--input") {
+        Some(i) if i + 1 < args.len() => Some(args.drain(i..i + 2).nth(1).unwrap()),
+        Some(_) => usage(),
+        None => None,
+    };
+    let numbers: Result<Vec<u32>, _> = args.iter().map(|a| a.parse()).collect();
+    let (year, day) = match numbers.as_deref() {
+        Ok([]) => (None, None),
+        Ok([year]) => (Some(*year), None),
+        Ok([year, day]) => (Some(*year), Some(*day)),
+        _ => usage(),
+    };
+
+    let solutions: Vec<_> = advent_bins::registry()
+        .values()
+        .filter(|s| year.is_none_or(|y| s.year == y) && day.is_none_or(|d| s.day == d))
+        .collect();
+    if solutions.is_empty() {
+        eprintln!("no solution registered for the given year and day");
+        process::exit(1);
+    }
+    let input = input.map(|path| {
+        if solutions.len() > 1 {
+            eprintln!("`--input` runs a single day, give the year and day");
+            process::exit(2);
+        }
+        advent_bins::read_file(Path::new(&path)).unwrap_or_else(|err| {
+            eprintln!("failed to read the input from `{path}`: {err}");
+            process::exit(1);
+        })
+    });
+
+    let mut failed = false;
+    for (i, solution) in solutions.iter().enumerate() {
+        if solutions.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("{:04}/{:02}", solution.year, solution.day);
+        }
+        let input = input.as_deref().unwrap_or_else(|| (solution.input)());
+        let result = match bench {
+            true => run_bench(solution, input),
+            false => run(solution, input),
+        };
+        if let Err(err) = result {
+            eprintln!(
+                "error: invalid input of {:04}/{:02} at {err}",
+                solution.year, solution.day
+            );
+            eprint!("{}", err.snippet());
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("usage: aoc [--bench] [--input PATH] [YEAR [DAY]]");
+    process::exit(2);
+}
+
+fn run(solution: &Solution, input: &str) -> Result<(), ParseError> {
+    let run = (solution.solve)(input)?;
+    println!("Parse: ({:.2?})", run.parse);
+    for (part, (answer, time)) in (1..).zip(&run.parts) {
+        println!("Part {part}: {answer} ({time:.2?})");
+    }
+    Ok(())
+}
+
+fn run_bench(solution: &Solution, input: &str) -> Result<(), ParseError> {
+    let start = Instant::now();
+    let mut runs: Vec<Run> = Vec::new();
+    while runs.len() < MIN_RUNS || start.elapsed() < BENCH_FOR {
+        runs.push((solution.solve)(input)?);
+    }
+    println!("Parse: ({})", stats(runs.iter().map(|r| r.parse)));
+    for (part, (answer, _)) in runs[0].parts.iter().enumerate() {
+        let times = runs.iter().map(|r| r.parts[part].1);
+        println!("Part {}: {answer} ({})", part + 1, stats(times));
+    }
+    Ok(())
+}
+
+/// Formats the mean and standard deviation of the times.
+fn stats(times: impl Iterator<Item = Duration>) -> String {
+    let times: Vec<f64> = times.map(|t| t.as_secs_f64()).collect();
+    let mean = times.iter().sum::<f64>() / times.len() as f64;
+    let variance = times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / times.len() as f64;
+    format!(
+        "{:.2?} ± {:.2?}",
+        Duration::from_secs_f64(mean),
+        Duration::from_secs_f64(variance.sqrt())
+    )
+}
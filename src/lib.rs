@@ -0,0 +1,234 @@
+//! The solutions, one module per day registered by year and day, and the code
+//! they share.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use std::{env, fs, io};
+
+use parse::ParseError;
+
+pub mod parse;
+
+/// A day's solution.
+///
+/// Every step takes the input as text, so that it can run on any input; the
+/// day's own one is returned by `input`.
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    /// Returns the day's puzzle input, see [`input!`].
+    pub input: fn() -> &'static str,
+    /// Parses the input without solving it, to check it.
+    pub parse: fn(&str) -> Result<(), ParseError>,
+    /// Parses the input and solves part 1.
+    pub part1: Part,
+    /// Parses the input and solves part 2, which the last day of a year does
+    /// not have.
+    pub part2: Option<Part>,
+    /// Parses the input once and solves every part, timing each step.
+    pub solve: fn(&str) -> Result<Run, ParseError>,
+}
+
+/// Parses an input and returns the answer to a part.
+pub type Part = fn(&str) -> Result<String, ParseError>;
+
+/// The answers of a solution and how long they took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub parse: Duration,
+    /// The answer to each part, in order.
+    pub parts: Vec<(String, Duration)>,
+}
+
+/// Registers the day's solution from its `parse_input` and part functions,
+/// e.g. `solution!(2024 / 06, part1, part2)`, and declares `default_input`
+/// for its tests, which parses the day's puzzle input.
+macro_rules! solution {
+    ($year:tt / $day:tt, $part1:ident $(, $part2:ident)? $(,)?) => {
+        #[cfg(test)]
+        fn default_input() -> Result<Input, $crate::parse::ParseError> {
+            parse_input($crate::input!($year / $day))
+        }
+
+        pub const SOLUTION: $crate::Solution = $crate::Solution {
+            year: $crate::number(stringify!($year)),
+            day: $crate::number(stringify!($day)),
+            input: || $crate::input!($year / $day),
+            parse: |input| parse_input(input).map(drop),
+            part1: |input| Ok($part1(parse_input(input)?).to_string()),
+            part2: part!($($part2)?),
+            solve: |input| {
+                let start = ::std::time::Instant::now();
+                let input = parse_input(input)?;
+                let parse = start.elapsed();
+                let parts = vec![
+                    $crate::timed(|| $part1(input.clone()).to_string()),
+                    $($crate::timed(|| $part2(input.clone()).to_string()),)?
+                ];
+                Ok($crate::Run { parse, parts })
+            },
+        };
+    };
+}
+
+/// The [`Part`] entry of an optional part function.
+macro_rules! part {
+    () => {
+        None
+    };
+    ($part:ident) => {
+        Some(|input| Ok($part(parse_input(input)?).to_string()))
+    };
+}
+
+/// Declares the module of every day from its file, and collects their
+/// solutions.
+macro_rules! solutions {
+    ($($year:ident = $dir:literal { $($day:ident = $file:literal,)* })*) => {
+        $(
+            #[path = $dir]
+            mod $year {
+                $(
+                    #[path = $file]
+                    mod $day;
+                )*
+
+                pub const SOLUTIONS: &[$crate::Solution] = &[$($day::SOLUTION),*];
+            }
+        )*
+
+        const SOLUTIONS: &[&[Solution]] = &[$($year::SOLUTIONS),*];
+    };
+}
+
+// `new` and `remove` edit this list, keep one day per line.
+solutions! {
+    y2024 = "../2024" {
+        d01 = "01.rs",
+        d02 = "02.rs",
+        d03 = "03.rs",
+        d04 = "04.rs",
+        d05 = "05.rs",
+        d06 = "06.rs",
+        d07 = "07.rs",
+        d08 = "08.rs",
+        d09 = "09.rs",
+        d10 = "10.rs",
+        d11 = "11.rs",
+        d12 = "12.rs",
+        d13 = "13.rs",
+        d14 = "14.rs",
+        d15 = "15.rs",
+        d16 = "16.rs",
+        d17 = "17.rs",
+        d18 = "18.rs",
+        d19 = "19.rs",
+        d20 = "20.rs",
+        d21 = "21.rs",
+        d22 = "22.rs",
+        d23 = "23.rs",
+        d24 = "24.rs",
+        d25 = "25.rs",
+    }
+    y2025 = "../2025" {
+        d01 = "01.rs",
+        d02 = "02.rs",
+        d03 = "03.rs",
+        d04 = "04.rs",
+        d05 = "05.rs",
+        d06 = "06.rs",
+        d07 = "07.rs",
+        d08 = "08.rs",
+        d09 = "09.rs",
+        d10 = "10.rs",
+        d11 = "11.rs",
+        d12 = "12.rs",
+    }
+}
+
+/// Returns every solution, keyed by year and day.
+pub fn registry() -> &'static BTreeMap<(u32, u32), Solution> {
+    static REGISTRY: OnceLock<BTreeMap<(u32, u32), Solution>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        SOLUTIONS
+            .iter()
+            .flat_map(|year| year.iter())
+            .map(|s| ((s.year, s.day), *s))
+            .collect()
+    })
+}
+
+/// Runs a part and returns its answer with how long it took.
+#[doc(hidden)]
+pub fn timed(part: impl FnOnce() -> String) -> (String, Duration) {
+    let start = Instant::now();
+    let answer = part();
+    (answer, start.elapsed())
+}
+
+/// Parses a year or day like `06`, which as a literal would look octal.
+#[doc(hidden)]
+pub const fn number(digits: &str) -> u32 {
+    let digits = digits.as_bytes();
+    let mut n = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        n = n * 10 + (digits[i] - b'0') as u32;
+        i += 1;
+    }
+    n
+}
+
+/// Returns the puzzle input of the day, e.g. `input!(2024 / 06)`.
+///
+/// The input is read from the file named by `ADVENT_INPUT`, or from stdin if
+/// it is `-`. Otherwise it is the input compiled in with the `default-inputs`
+/// feature, or else `input/{year}/{day}.txt` read when the binary runs.
+#[macro_export]
+macro_rules! input {
+    ($year:tt / $day:tt) => {{
+        static INPUT: ::std::sync::OnceLock<::std::borrow::Cow<'static, str>> =
+            ::std::sync::OnceLock::new();
+        #[cfg(feature = "default-inputs")]
+        let default = ::core::option::Option::Some(::advent::include_input!($year / $day));
+        #[cfg(not(feature = "default-inputs"))]
+        let default = ::core::option::Option::None;
+        let input: &'static str = INPUT.get_or_init(|| {
+            $crate::read_input(concat!(stringify!($year), "/", stringify!($day)), default)
+        });
+        input
+    }};
+}
+
+/// Reads an input from a file, or from stdin if the path is `-`.
+pub fn read_file(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        return Ok(input);
+    }
+    fs::read_to_string(path)
+}
+
+#[doc(hidden)]
+pub fn read_input(day: &str, default: Option<&'static str>) -> Cow<'static, str> {
+    let path = match env::var_os("ADVENT_INPUT") {
+        Some(path) => PathBuf::from(path),
+        None => match default {
+            Some(input) => return Cow::Borrowed(input),
+            None => Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("input/{day}.txt")),
+        },
+    };
+    match read_file(&path) {
+        Ok(input) => Cow::Owned(input),
+        Err(err) => panic!(
+            "failed to read the input of {day} from `{}`: {err}",
+            path.display()
+        ),
+    }
+}
@@ -0,0 +1,202 @@
+//! Parse errors that point at the offending text of the input.
+//!
+//! Parsers keep slicing the input rather than copying it, so an error can be
+//! located from the slice it is about.
+
+use std::fmt::{self, Display, Write};
+use std::str::FromStr;
+
+use advent_geometry::{Grid, GridError};
+use regex::{Captures, Regex};
+
+/// An error in the input, with where it is and the text it is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: usize,
+    /// The offending text, empty if something is missing.
+    pub text: String,
+    /// The whole line the error is on.
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// Renders the line of the error with the offending text underlined.
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let width = self.text.lines().next().unwrap_or_default().chars().count();
+        let mut out = String::new();
+        writeln!(out, "{gutter} |").unwrap();
+        writeln!(out, "{number} | {}", self.source_line).unwrap();
+        writeln!(
+            out,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(width.max(1))
+        )
+        .unwrap();
+        out
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The input being parsed, to locate errors in.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input }
+    }
+
+    /// Returns an error about `at`, a slice of the input.
+    ///
+    /// Text that is not a slice of the input, e.g. after copying it, is
+    /// looked up in the input instead.
+    pub fn error(&self, at: &str, message: impl Display) -> ParseError {
+        let start = self.input.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + at.len() <= self.input.len())
+            .or_else(|| self.input.find(at))
+            .unwrap_or(self.input.len());
+
+        let line_start = self.input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let source_line = self.input[line_start..].lines().next().unwrap_or_default();
+        ParseError {
+            message: message.to_string(),
+            line: self.input[..offset].matches('\n').count() + 1,
+            column: self.input[line_start..offset].chars().count() + 1,
+            text: at.to_owned(),
+            source_line: source_line.to_owned(),
+        }
+    }
+
+    /// Returns an error about the input ending too early.
+    pub fn end_of_input(&self) -> ParseError {
+        self.missing("unexpected end of input")
+    }
+
+    /// Returns an error about something missing from the input, pointing at
+    /// its end.
+    pub fn missing(&self, message: impl Display) -> ParseError {
+        let trimmed = self.input.trim_end();
+        self.error(&trimmed[trimmed.len()..], message)
+    }
+
+    /// Parses `s`, a slice of the input.
+    pub fn parse<T>(&self, s: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        s.parse()
+            .map_err(|err| self.error(s, format!("cannot parse `{s}`: {err}")))
+    }
+
+    /// Matches the regex against `line`, a slice of the input.
+    pub fn captures<'h>(&self, re: &Regex, line: &'h str) -> Result<Captures<'h>, ParseError> {
+        re.captures(line)
+            .ok_or_else(|| self.error(line, format!("expected a match of `{re}`")))
+    }
+
+    /// Parses `text`, a slice of the input, into a grid with a cell per
+    /// character.
+    pub fn grid<T>(
+        &self,
+        text: &'a str,
+        cell: impl FnMut(char, &'a str) -> Result<T, ParseError>,
+    ) -> Result<Grid<T>, ParseError> {
+        Grid::parse_with(text, cell).map_err(|err| match err {
+            GridError::Ragged(row) => self.error(row, "row not as wide as the first one"),
+            GridError::Cell(err) => err,
+        })
+    }
+
+    /// Reads the fields of `line`, a slice of the input, one by one.
+    pub fn fields<I>(&self, line: &'a str, fields: I) -> Fields<'a, I>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        Fields {
+            source: *self,
+            line,
+            fields,
+        }
+    }
+}
+
+/// The fields of a line, which is reported as too short when one is missing.
+#[derive(Debug, Clone)]
+pub struct Fields<'a, I> {
+    source: Source<'a>,
+    line: &'a str,
+    fields: I,
+}
+
+impl<'a, I: Iterator<Item = &'a str>> Fields<'a, I> {
+    /// Returns the next field.
+    pub fn field(&mut self) -> Result<&'a str, ParseError> {
+        self.fields.next().ok_or_else(|| {
+            let end = &self.line[self.line.len()..];
+            self.source.error(end, "unexpected end of line")
+        })
+    }
+
+    /// Parses the next field.
+    pub fn parse<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let field = self.field()?;
+        self.source.parse(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_errors() {
+        let input = "1 2\n3 x4\n";
+        let source = Source::new(input);
+        let line = input.lines().nth(1).unwrap();
+        let mut fields = source.fields(line, line.split(' '));
+        assert_eq!(fields.parse::<i32>(), Ok(3));
+        let err = fields.parse::<i32>().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "x4");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: cannot parse `x4`: invalid digit found in string"
+        );
+        assert_eq!(err.snippet(), "  |\n2 | 3 x4\n  |   ^^\n");
+
+        let err = fields.field().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.snippet(), "  |\n2 | 3 x4\n  |     ^\n");
+
+        let err = source.end_of_input();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(source.error("x4", "not a slice").column, 3);
+    }
+}